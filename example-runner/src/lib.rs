@@ -29,7 +29,7 @@ pub fn run(desc: ExampleDescriptor, f: impl FnMut(&mut Scene, Size2<f32>)) -> Re
         .ok_or(RunError::NoSuitableAdapter)?;
     let (device, queue) = adapter.request_device(&Default::default(), None).block_on()?;
 
-    let window_state = WindowState::Uninit(window_attributes);
+    let window_state = WindowState::Uninit(Box::new(window_attributes));
     let renderer = None;
     let text_context = TextContext::default();
 
@@ -93,7 +93,7 @@ struct Impl<F> {
 
 enum WindowState {
     /// [`ApplicationHandler::resumed`] hasn't been called yet.
-    Uninit(WindowAttributes),
+    Uninit(Box<WindowAttributes>),
     /// The application has been suspended, the surface has been invalidated and
     /// needs to be recreated.
     Suspended(Arc<Window>),
@@ -109,7 +109,7 @@ where
         let mut window_state = self.window_state.take();
 
         if let WindowState::Uninit(attributes) = window_state {
-            let window = Arc::new(event_loop.create_window(attributes.clone()).unwrap());
+            let window = Arc::new(event_loop.create_window(*attributes).unwrap());
             let surface = self.instance.create_surface(window.clone()).unwrap();
 
             window_state = WindowState::Init { window, surface };
@@ -182,7 +182,9 @@ where
                     self.scene.clear();
                     (self.f)(&mut self.scene, size);
 
-                    renderer.prepare(&mut self.text_context, &self.scene, global_transform);
+                    renderer
+                        .prepare(&mut self.text_context, &self.scene, global_transform)
+                        .unwrap();
                     renderer
                         .render_to_surface(&self.device, &self.queue, &texture, &Default::default())
                         .unwrap();
@@ -204,6 +206,6 @@ impl WindowState {
     fn take(&mut self) -> Self {
         // a little jank
 
-        mem::replace(self, Self::Uninit(WindowAttributes::default()))
+        mem::replace(self, Self::Uninit(Default::default()))
     }
 }
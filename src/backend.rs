@@ -0,0 +1,178 @@
+use crate::element::{
+    Brush,
+    FillStyle,
+    Image,
+    ImageQuality,
+    Layer,
+    Path,
+    Source,
+    StrokeStyle,
+    TextStyle,
+};
+#[cfg(feature = "text")]
+use crate::element::{GlyphRun, TextLayout};
+#[cfg(feature = "text")]
+use crate::math::Point2;
+use crate::math::Rect;
+use crate::{Command, Scene, SceneError};
+
+/// A target that a [`Scene`] can be replayed into.
+///
+/// Each method corresponds to a [`Command`]. Layers are always balanced when
+/// replayed with [`Scene::replay`], and the transform of a [`Layer`] applies to
+/// everything drawn until the matching call to [`Backend::pop_layer`].
+pub trait Backend {
+    /// Fills a path.
+    fn fill(&mut self, path: &Path, brush: &Brush, style: &FillStyle);
+
+    /// Strokes a path.
+    fn stroke(&mut self, path: &Path, brush: &Brush, style: &StrokeStyle);
+
+    /// Draws text within bounds.
+    fn draw_text(&mut self, source: &Source, bounds: Rect<f32>, style: &TextStyle);
+
+    /// Draws an image scaled to fit within `bounds`.
+    fn draw_image(&mut self, image: &Image, bounds: Rect<f32>, quality: ImageQuality);
+
+    /// Draws a run of glyphs.
+    ///
+    /// By default, this fills the outlines of the glyphs.
+    #[cfg(feature = "text")]
+    fn draw_glyph_run(&mut self, glyph_run: &GlyphRun) {
+        self.fill(&glyph_run.outline(), &glyph_run.brush, &FillStyle::default());
+    }
+
+    /// Draws a [`TextLayout`].
+    ///
+    /// By default, this draws each of its glyph runs.
+    #[cfg(feature = "text")]
+    fn draw_text_layout(&mut self, layout: &TextLayout, origin: Point2<f32>) {
+        for glyph_run in layout.glyph_runs(origin) {
+            self.draw_glyph_run(&glyph_run);
+        }
+    }
+
+    /// Pushes a new layer.
+    fn push_layer(&mut self, layer: &Layer);
+
+    /// Pops the most recently pushed layer.
+    fn pop_layer(&mut self);
+}
+
+impl Scene {
+    /// Replays the commands in this scene into a [`Backend`].
+    ///
+    /// Returns an error without replaying anything if the scene has unbalanced
+    /// layers (see [`Scene::finish`]).
+    pub fn replay(&self, backend: &mut impl Backend) -> Result<(), SceneError> {
+        self.finish()?;
+        self.replay_unchecked(backend);
+
+        Ok(())
+    }
+
+    /// Replays the commands in this scene into a [`Backend`] without checking
+    /// that its layers are balanced.
+    pub(crate) fn replay_unchecked(&self, backend: &mut impl Backend) {
+        for command in self {
+            match command {
+                Command::Fill { path, brush, style } => backend.fill(path, brush, style),
+                Command::Stroke { path, brush, style } => backend.stroke(path, brush, style),
+                Command::DrawText { source, bounds, style } => {
+                    backend.draw_text(source, *bounds, style);
+                },
+                Command::DrawImage { image, bounds, quality } => {
+                    backend.draw_image(image, *bounds, *quality);
+                },
+                #[cfg(feature = "text")]
+                Command::DrawTextLayout { layout, origin } => {
+                    backend.draw_text_layout(layout, *origin);
+                },
+                Command::PushLayer(layer) => backend.push_layer(layer),
+                Command::PopLayer => backend.pop_layer(),
+            }
+        }
+    }
+}
+
+/// Records the replayed commands.
+impl Backend for Scene {
+    fn fill(&mut self, path: &Path, brush: &Brush, style: &FillStyle) {
+        Scene::fill(self, path.clone(), brush.clone(), *style);
+    }
+
+    fn stroke(&mut self, path: &Path, brush: &Brush, style: &StrokeStyle) {
+        Scene::stroke(self, path.clone(), brush.clone(), style.clone());
+    }
+
+    fn draw_text(&mut self, source: &Source, bounds: Rect<f32>, style: &TextStyle) {
+        Scene::draw_text(self, source.clone(), bounds, style.clone());
+    }
+
+    fn draw_image(&mut self, image: &Image, bounds: Rect<f32>, quality: ImageQuality) {
+        Scene::draw_image(self, image.clone(), bounds, quality);
+    }
+
+    #[cfg(feature = "text")]
+    fn draw_text_layout(&mut self, layout: &TextLayout, origin: Point2<f32>) {
+        Scene::draw_text_layout(self, layout.clone(), origin);
+    }
+
+    fn push_layer(&mut self, layer: &Layer) {
+        Scene::push_layer(self, layer.clone());
+    }
+
+    fn pop_layer(&mut self) {
+        // popping more layers than were pushed is a bug in the caller, but
+        // shouldn't corrupt the recording
+        _ = Scene::pop_layer(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Color;
+    use crate::math::{Affine2, Point2, Size2, Vec2};
+
+    #[test]
+    fn replaying_into_a_scene_records_the_same_commands() {
+        let mut scene = Scene::new();
+        let rect = Rect::new(Point2::new(0.0, 0.0), Size2::new(10.0, 10.0));
+
+        scene.fill(rect, Color::BLACK, FillStyle::default());
+        scene.with_layer(
+            Layer {
+                transform: Affine2::from_translation(Vec2::new(5.0, 5.0)),
+                blend_mode: Default::default(),
+                clip: Some(rect.into()),
+                alpha: 0.5,
+            },
+            |scene| scene.stroke(rect, Color::WHITE, StrokeStyle::default()),
+        );
+
+        let mut recording = Scene::new();
+
+        scene.replay(&mut recording).unwrap();
+
+        assert_eq!(format!("{:?}", recording.commands()), format!("{:?}", scene.commands()));
+        assert_eq!(recording.finish(), Ok(()));
+    }
+
+    #[test]
+    fn unbalanced_scenes_are_not_replayed() {
+        let mut scene = Scene::new();
+
+        scene.push_layer(Layer {
+            transform: Affine2::IDENTITY,
+            blend_mode: Default::default(),
+            clip: None,
+            alpha: 1.0,
+        });
+
+        let mut recording = Scene::new();
+
+        assert_eq!(scene.replay(&mut recording), Err(SceneError::UnclosedLayers(1)));
+        assert!(recording.commands().is_empty());
+    }
+}
@@ -0,0 +1,1019 @@
+use std::f32::consts::{SQRT_2, TAU};
+
+#[cfg(feature = "png")]
+use thiserror::Error;
+use tiny_skia::{
+    FilterQuality,
+    GradientStop,
+    IntRect,
+    IntSize,
+    Mask,
+    Paint,
+    Pixmap,
+    PixmapPaint,
+    Shader,
+    SpreadMode,
+    Transform,
+};
+
+use crate::element::{
+    BlendMode,
+    Brush,
+    Cap,
+    Color,
+    ColorStop,
+    Compose,
+    Extend,
+    FillRule,
+    FillStyle,
+    Image,
+    ImageBrush,
+    ImageQuality,
+    Join,
+    Layer,
+    LinearGradient,
+    Mix,
+    Path,
+    PathElement,
+    RadialGradient,
+    Source,
+    StrokeStyle,
+    SweepGradient,
+    TextContext,
+    TextLayout,
+    TextStyle,
+};
+#[cfg(feature = "png")]
+use crate::element::{PngDescriptor, PngError};
+use crate::math::{Affine2, Mat2, Point2, Rect, Size2, Vec2};
+use crate::util::max_scale;
+use crate::{Backend, Scene, SceneError};
+
+/// The maximum distance in pixels between a curve and the lines it's flattened
+/// into, where tiny-skia can't flatten it itself.
+const TOLERANCE: f32 = 0.1;
+
+/// A renderer for a [`Scene`] that rasterizes on the CPU.
+///
+/// Unlike [`Renderer`](crate::Renderer), this doesn't require a GPU.
+#[derive(Default)]
+pub struct CpuRenderer {
+    ops: Vec<Op>,
+}
+
+/// Parameters for calling [`CpuRenderer::render_to_image`].
+#[derive(Clone, Copy)]
+pub struct CpuRenderDescriptor {
+    /// The base color.
+    pub clear_color: Color,
+}
+
+/// Error when calling [`Scene::render_to_png`] or
+/// [`Scene::render_to_png_file`].
+#[cfg(feature = "png")]
+#[derive(Debug, Error)]
+pub enum CpuRendererError {
+    #[error(transparent)]
+    Scene(#[from] SceneError),
+    #[error(transparent)]
+    Png(#[from] PngError),
+}
+
+/// A prepared drawing operation.
+enum Op {
+    Fill { path: tiny_skia::Path, brush: Brush, rule: tiny_skia::FillRule, transform: Transform },
+    Stroke { path: tiny_skia::Path, brush: Brush, stroke: tiny_skia::Stroke, transform: Transform },
+    DrawImage { image: Image, bounds: Rect<f32>, quality: ImageQuality, transform: Transform },
+    PushLayer { clip: Option<(tiny_skia::Path, Transform)>, blend_mode: BlendMode, alpha: f32 },
+    PopLayer,
+}
+
+/// A layer that is being drawn to.
+struct OpenLayer {
+    pixmap: Pixmap,
+    clip: Option<Mask>,
+    blend_mode: BlendMode,
+    alpha: f32,
+}
+
+impl CpuRenderer {
+    /// Creates a new CPU renderer.
+    pub fn new() -> Self {
+        Self { ops: Vec::new() }
+    }
+
+    /// Encodes scene data.
+    ///
+    /// Returns an error if the scene has unbalanced layers (see
+    /// [`Scene::finish`]).
+    pub fn prepare(
+        &mut self,
+        text_cx: &mut TextContext,
+        scene: &Scene,
+        global_transform: Affine2<f32>,
+    ) -> Result<(), SceneError> {
+        self.ops.clear();
+
+        scene.replay(&mut CpuBackend {
+            ops: &mut self.ops,
+            text_cx,
+            transforms: vec![global_transform.into()],
+        })
+    }
+
+    /// Renders prepared data to a new [`Image`].
+    ///
+    /// The returned image has straight (not premultiplied) alpha. All
+    /// rendering is anti-aliased analytically.
+    pub fn render_to_image(&mut self, size: Size2<u32>, desc: &CpuRenderDescriptor) -> Image {
+        let Size2 { w: width, h: height } = size;
+
+        let Some(mut base) = Pixmap::new(width, height) else {
+            return Image::new(Vec::new(), width, height).expect("image is empty");
+        };
+        let mut layers: Vec<OpenLayer> = Vec::new();
+
+        base.fill(desc.clear_color.into());
+
+        for op in &self.ops {
+            let target = layers.last_mut().map(|layer| &mut layer.pixmap).unwrap_or(&mut base);
+
+            match op {
+                Op::Fill { path, brush, rule, transform } => {
+                    let mut pattern = None;
+                    let Some(area) = device_bounds(path, *transform, 0.0, size) else {
+                        continue;
+                    };
+
+                    if let Some(paint) = paint(brush, *transform, area, &mut pattern) {
+                        target.fill_path(path, &paint, *rule, *transform, None);
+                    }
+                },
+                Op::Stroke { path, brush, stroke, transform } => {
+                    let mut pattern = None;
+                    // joins and caps don't extend further than this from the path
+                    let reach = stroke.width / 2.0 * stroke.miter_limit.max(SQRT_2);
+                    let Some(area) = device_bounds(path, *transform, reach, size) else {
+                        continue;
+                    };
+
+                    if let Some(paint) = paint(brush, *transform, area, &mut pattern) {
+                        target.stroke_path(path, &paint, stroke, *transform, None);
+                    }
+                },
+                Op::DrawImage { image, bounds, quality, transform } => {
+                    if let Some(pixmap) = to_pixmap(image) {
+                        let transform =
+                            transform.pre_translate(bounds.origin.x, bounds.origin.y).pre_scale(
+                                bounds.size.w / image.width() as f32,
+                                bounds.size.h / image.height() as f32,
+                            );
+                        let paint =
+                            PixmapPaint { quality: (*quality).into(), ..Default::default() };
+
+                        target.draw_pixmap(0, 0, pixmap.as_ref(), &paint, transform, None);
+                    }
+                },
+                Op::PushLayer { clip, blend_mode, alpha } => {
+                    let clip = clip.as_ref().map(|(path, transform)| {
+                        let mut mask = Mask::new(width, height).unwrap();
+
+                        mask.fill_path(path, tiny_skia::FillRule::Winding, true, *transform);
+
+                        mask
+                    });
+
+                    layers.push(OpenLayer {
+                        pixmap: Pixmap::new(width, height).unwrap(),
+                        clip,
+                        blend_mode: *blend_mode,
+                        alpha: *alpha,
+                    });
+                },
+                Op::PopLayer => {
+                    let OpenLayer { pixmap, clip, blend_mode, alpha } = layers.pop().unwrap();
+                    let target =
+                        layers.last_mut().map(|layer| &mut layer.pixmap).unwrap_or(&mut base);
+
+                    target.draw_pixmap(
+                        0,
+                        0,
+                        pixmap.as_ref(),
+                        &PixmapPaint {
+                            opacity: alpha,
+                            blend_mode: blend_mode.into(),
+                            quality: FilterQuality::Nearest,
+                        },
+                        Transform::identity(),
+                        clip.as_ref(),
+                    );
+                },
+            }
+        }
+
+        let image = Image::new(base.take(), width, height)
+            .expect("pixmap is the size of the image")
+            .with_premultiplied(true);
+
+        Image::new(image.straight_data().into_owned(), width, height)
+            .expect("pixmap is the size of the image")
+    }
+
+    /// Renders prepared data and encodes it as PNG.
+    #[cfg(feature = "png")]
+    pub fn render_to_png(
+        &mut self,
+        size: Size2<u32>,
+        desc: &CpuRenderDescriptor,
+        png_desc: &PngDescriptor,
+    ) -> Result<Vec<u8>, PngError> {
+        self.render_to_image(size, desc).encode_png(png_desc)
+    }
+
+    /// Renders prepared data and saves it as a PNG file.
+    #[cfg(feature = "png")]
+    pub fn render_to_png_file(
+        &mut self,
+        size: Size2<u32>,
+        desc: &CpuRenderDescriptor,
+        png_desc: &PngDescriptor,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), PngError> {
+        self.render_to_image(size, desc).save_png(path, png_desc)
+    }
+}
+
+#[cfg(feature = "png")]
+impl Scene {
+    /// Renders this scene with a [`CpuRenderer`] and encodes it as PNG.
+    ///
+    /// Returns an error if the scene has unbalanced layers (see
+    /// [`Scene::finish`]).
+    pub fn render_to_png(
+        &self,
+        text_cx: &mut TextContext,
+        size: Size2<u32>,
+        desc: &CpuRenderDescriptor,
+        png_desc: &PngDescriptor,
+    ) -> Result<Vec<u8>, CpuRendererError> {
+        let mut renderer = CpuRenderer::new();
+
+        renderer.prepare(text_cx, self, Affine2::IDENTITY)?;
+
+        Ok(renderer.render_to_png(size, desc, png_desc)?)
+    }
+
+    /// Renders this scene with a [`CpuRenderer`] and saves it as a PNG file.
+    ///
+    /// Returns an error if the scene has unbalanced layers (see
+    /// [`Scene::finish`]).
+    pub fn render_to_png_file(
+        &self,
+        text_cx: &mut TextContext,
+        size: Size2<u32>,
+        desc: &CpuRenderDescriptor,
+        png_desc: &PngDescriptor,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), CpuRendererError> {
+        let mut renderer = CpuRenderer::new();
+
+        renderer.prepare(text_cx, self, Affine2::IDENTITY)?;
+
+        Ok(renderer.render_to_png_file(size, desc, png_desc, path)?)
+    }
+}
+
+/// Records a [`Scene`] as [`Op`]s.
+struct CpuBackend<'a> {
+    ops: &'a mut Vec<Op>,
+    text_cx: &'a mut TextContext,
+    /// The transform of each open layer, applied to everything within it.
+    transforms: Vec<Transform>,
+}
+
+impl CpuBackend<'_> {
+    fn transform(&self) -> Transform {
+        *self.transforms.last().unwrap()
+    }
+}
+
+impl Backend for CpuBackend<'_> {
+    fn fill(&mut self, path: &Path, brush: &Brush, style: &FillStyle) {
+        let FillStyle { rule } = *style;
+
+        let brush = extend_image(brush, path.bounds());
+
+        if let Some(path) = to_tiny_skia_path(path) {
+            let rule = rule.into();
+            let transform = self.transform();
+
+            self.ops.push(Op::Fill { path, brush, rule, transform });
+        }
+    }
+
+    fn stroke(&mut self, path: &Path, brush: &Brush, style: &StrokeStyle) {
+        // tiny-skia only supports a single cap for both ends, so the outline of
+        // a stroke with different caps is filled instead
+        if style.start != style.end {
+            let Transform { sx, kx, ky, sy, .. } = self.transform();
+            let scale = max_scale(Mat2::from_columns(Vec2::new(sx, ky), Vec2::new(kx, sy)));
+
+            if scale > 0.0 {
+                let outline = path.stroke_outline(style, TOLERANCE / scale);
+
+                self.fill(&outline, brush, &FillStyle { rule: FillRule::NonZero });
+            }
+
+            return;
+        }
+
+        let brush = extend_image(brush, path.bounds().with_insets(Vec2::splat(-style.reach())));
+
+        if let Some(path) = to_tiny_skia_path(path) {
+            let stroke = style.into();
+            let transform = self.transform();
+
+            self.ops.push(Op::Stroke { path, brush, stroke, transform });
+        }
+    }
+
+    fn draw_text(&mut self, source: &Source, bounds: Rect<f32>, style: &TextStyle) {
+        let mut layout = TextLayout::new();
+
+        // TODO: respect vertical bounds
+        layout.build(self.text_cx, source, style.clone());
+        layout.break_lines(bounds.size.w, style.alignment);
+
+        self.draw_text_layout(&layout, bounds.origin);
+    }
+
+    fn draw_image(&mut self, image: &Image, bounds: Rect<f32>, quality: ImageQuality) {
+        let image = image.clone();
+        let transform = self.transform();
+
+        self.ops.push(Op::DrawImage { image, bounds, quality, transform });
+    }
+
+    fn push_layer(&mut self, layer: &Layer) {
+        let Layer { transform: layer_transform, blend_mode, clip, alpha } = layer;
+
+        let transform = self.transform().pre_concat((*layer_transform).into());
+        let (clip, alpha) = match clip.as_ref().map(to_tiny_skia_path) {
+            Some(Some(clip)) => (Some((clip, transform)), *alpha),
+            // an empty clip path clips everything
+            Some(None) => (None, 0.0),
+            None => (None, *alpha),
+        };
+
+        self.ops.push(Op::PushLayer { clip, blend_mode: *blend_mode, alpha });
+        self.transforms.push(transform);
+    }
+
+    fn pop_layer(&mut self) {
+        self.ops.push(Op::PopLayer);
+        self.transforms.pop();
+    }
+}
+
+/// Extends the image of an image brush over the bounds it paints if it has
+/// different extend modes per axis, as tiny-skia only supports a single one.
+fn extend_image(brush: &Brush, bounds: Rect<f32>) -> Brush {
+    match brush {
+        Brush::Image(image_brush) if image_brush.extend_x != image_brush.extend_y => {
+            match image_brush.extend_over(bounds, 1) {
+                Some(image_brush) => Brush::Image(image_brush),
+                None => brush.clone(),
+            }
+        },
+        _ => brush.clone(),
+    }
+}
+
+/// Returns the pixels that a path can cover within a target of some size, with
+/// `outset` added around it in user space.
+fn device_bounds(
+    path: &tiny_skia::Path,
+    transform: Transform,
+    outset: f32,
+    size: Size2<u32>,
+) -> Option<IntRect> {
+    let bounds = path.bounds().outset(outset, outset)?.transform(transform)?.round_out()?;
+
+    bounds.intersect(&IntRect::from_xywh(0, 0, size.w, size.h)?)
+}
+
+/// Returns the paint for a brush.
+///
+/// Brushes that tiny-skia can't express are shaded per-pixel into `pattern`,
+/// within the pixels of `area`.
+fn paint<'a>(
+    brush: &Brush,
+    transform: Transform,
+    area: IntRect,
+    pattern: &'a mut Option<Pixmap>,
+) -> Option<Paint<'a>> {
+    let shader = match brush {
+        Brush::Solid(color) => Shader::SolidColor((*color).into()),
+        Brush::LinearGradient(LinearGradient { start, end, extend, stops }) => {
+            tiny_skia::LinearGradient::new(
+                tiny_skia::Point::from_xy(start.x, start.y),
+                tiny_skia::Point::from_xy(end.x, end.y),
+                stops
+                    .iter()
+                    .map(|stop| GradientStop::new(stop.offset, stop.color.into()))
+                    .collect(),
+                (*extend).into(),
+                Transform::identity(),
+            )?
+        },
+        Brush::RadialGradient(RadialGradient {
+            start_center,
+            start_radius,
+            end_center,
+            end_radius,
+            extend,
+            stops,
+        }) => {
+            let center_delta = *end_center - *start_center;
+            let radius_delta = end_radius - start_radius;
+
+            shade(area, transform, stops, *extend, pattern, |point| {
+                two_point_conical(
+                    point - *start_center,
+                    center_delta.to_vec(),
+                    *start_radius,
+                    radius_delta,
+                )
+            })?
+        },
+        Brush::SweepGradient(SweepGradient { center, start_angle, end_angle, extend, stops }) => {
+            let sweep = end_angle - start_angle;
+
+            if sweep == 0.0 {
+                return None;
+            }
+
+            shade(area, transform, stops, *extend, pattern, |point| {
+                let delta = point - *center;
+                let angle = delta.y.atan2(delta.x).rem_euclid(TAU);
+
+                Some((angle - start_angle) / sweep)
+            })?
+        },
+        Brush::Image(ImageBrush { image, transform, extend_x, extend_y: _, quality }) => {
+            // the backend has already extended images with different extend modes per
+            // axis over what they paint
+            let pixmap = pattern.insert(to_pixmap(image)?);
+
+            tiny_skia::Pattern::new(
+                pixmap.as_ref(),
+                (*extend_x).into(),
+                (*quality).into(),
+                1.0,
+                (*transform).into(),
+            )
+        },
+    };
+
+    Some(Paint { shader, anti_alias: true, ..Default::default() })
+}
+
+/// Shades a gradient per-pixel within `area`, returning a pattern shader that
+/// draws it.
+///
+/// `f` maps a point in user space to an offset into the gradient.
+fn shade<'a>(
+    area: IntRect,
+    transform: Transform,
+    stops: &[ColorStop],
+    extend: Extend,
+    pattern: &'a mut Option<Pixmap>,
+    f: impl Fn(Point2<f32>) -> Option<f32>,
+) -> Option<Shader<'a>> {
+    let inverse = transform.invert()?;
+    let mut pixmap = Pixmap::new(area.width(), area.height())?;
+
+    for (idx, pixel) in pixmap.pixels_mut().iter_mut().enumerate() {
+        let mut point = tiny_skia::Point::from_xy(
+            (area.x() + (idx as u32 % area.width()) as i32) as f32 + 0.5,
+            (area.y() + (idx as u32 / area.width()) as i32) as f32 + 0.5,
+        );
+
+        inverse.map_point(&mut point);
+
+        if let Some(offset) = f(Point2::new(point.x, point.y)) {
+            let color: tiny_skia::Color = sample(stops, extend, offset).into();
+
+            *pixel = color.premultiply().to_color_u8();
+        }
+    }
+
+    let pixmap = pattern.insert(pixmap);
+
+    // cancel out the transform of the path, as the pixmap is in device space
+    Some(tiny_skia::Pattern::new(
+        pixmap.as_ref(),
+        SpreadMode::Pad,
+        FilterQuality::Nearest,
+        1.0,
+        inverse.pre_translate(area.x() as f32, area.y() as f32),
+    ))
+}
+
+/// Returns the offset into a two-point conical gradient for a point relative to
+/// the start center, or `None` if the point isn't covered.
+fn two_point_conical(
+    point: Point2<f32>,
+    center_delta: Vec2<f32>,
+    start_radius: f32,
+    radius_delta: f32,
+) -> Option<f32> {
+    // solve `|point - t * center_delta| = start_radius + t * radius_delta` for
+    // the largest `t` with a non-negative radius
+    let a = center_delta.x * center_delta.x + center_delta.y * center_delta.y
+        - radius_delta * radius_delta;
+    let b = point.x * center_delta.x + point.y * center_delta.y + start_radius * radius_delta;
+    let c = point.x * point.x + point.y * point.y - start_radius * start_radius;
+
+    let is_valid = |t: f32| start_radius + t * radius_delta >= 0.0;
+
+    if a.abs() < f32::EPSILON {
+        let t = c / (2.0 * b);
+
+        return (b != 0.0 && is_valid(t)).then_some(t);
+    }
+
+    let discriminant = b * b - a * c;
+
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let root = discriminant.sqrt();
+    let (t0, t1) = ((b + root) / a, (b - root) / a);
+    let (max, min) = if t0 > t1 { (t0, t1) } else { (t1, t0) };
+
+    if is_valid(max) {
+        Some(max)
+    } else {
+        is_valid(min).then_some(min)
+    }
+}
+
+/// Returns the color of a gradient at an offset.
+fn sample(stops: &[ColorStop], extend: Extend, offset: f32) -> Color {
+    let offset = match extend {
+        Extend::Pad => offset.clamp(0.0, 1.0),
+        Extend::Repeat => offset.rem_euclid(1.0),
+        Extend::Reflect => {
+            let offset = offset.rem_euclid(2.0);
+
+            if offset > 1.0 {
+                2.0 - offset
+            } else {
+                offset
+            }
+        },
+    };
+
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return Color::TRANSPARENT;
+    };
+
+    if offset <= first.offset {
+        return first.color;
+    }
+
+    for window in stops.windows(2) {
+        let [start, end] = [window[0], window[1]];
+
+        if offset <= end.offset {
+            let range = end.offset - start.offset;
+            let t = if range > 0.0 { (offset - start.offset) / range } else { 1.0 };
+            let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+            return Color::rgba(
+                lerp(start.color.r, end.color.r),
+                lerp(start.color.g, end.color.g),
+                lerp(start.color.b, end.color.b),
+                lerp(start.color.a, end.color.a),
+            );
+        }
+    }
+
+    last.color
+}
+
+/// Converts an image to a premultiplied pixmap.
+fn to_pixmap(image: &Image) -> Option<Pixmap> {
+    let size = IntSize::from_wh(image.width(), image.height())?;
+    let mut data = image.data().to_vec();
+
+    if !image.is_premultiplied() {
+        for pixel in data.chunks_exact_mut(4) {
+            let a = pixel[3] as u16;
+
+            for channel in &mut pixel[..3] {
+                *channel = ((*channel as u16 * a + 127) / 255) as u8;
+            }
+        }
+    }
+
+    Pixmap::from_vec(data, size)
+}
+
+/// Converts a path, returning `None` if it's empty.
+fn to_tiny_skia_path(path: &Path) -> Option<tiny_skia::Path> {
+    let mut builder = tiny_skia::PathBuilder::new();
+
+    for element in path {
+        match element {
+            PathElement::Open(p) => builder.move_to(p.x, p.y),
+            PathElement::LineTo(p) => builder.line_to(p.x, p.y),
+            PathElement::QuadTo { p, c } => builder.quad_to(p.x, p.y, c.x, c.y),
+            PathElement::CubicTo { p, c1, c2 } => {
+                builder.cubic_to(p.x, p.y, c1.x, c1.y, c2.x, c2.y)
+            },
+            PathElement::Close => builder.close(),
+        }
+    }
+
+    builder.finish()
+}
+
+impl Default for CpuRenderDescriptor {
+    fn default() -> Self {
+        Self { clear_color: Color::TRANSPARENT }
+    }
+}
+
+impl From<Affine2<f32>> for Transform {
+    fn from(transform: Affine2<f32>) -> Self {
+        let Affine2 { transform: Mat2 { x, y }, translation } = transform;
+
+        Transform::from_row(x.x, x.y, y.x, y.y, translation.x, translation.y)
+    }
+}
+
+impl From<Color> for tiny_skia::Color {
+    fn from(color: Color) -> Self {
+        let Color { r, g, b, a } = color;
+
+        tiny_skia::Color::from_rgba(
+            r.clamp(0.0, 1.0),
+            g.clamp(0.0, 1.0),
+            b.clamp(0.0, 1.0),
+            a.clamp(0.0, 1.0),
+        )
+        .unwrap()
+    }
+}
+
+impl From<FillRule> for tiny_skia::FillRule {
+    fn from(fill_rule: FillRule) -> Self {
+        match fill_rule {
+            FillRule::NonZero => tiny_skia::FillRule::Winding,
+            FillRule::EvenOdd => tiny_skia::FillRule::EvenOdd,
+        }
+    }
+}
+
+impl From<StrokeStyle> for tiny_skia::Stroke {
+    fn from(stroke_style: StrokeStyle) -> Self {
+        (&stroke_style).into()
+    }
+}
+
+impl From<&StrokeStyle> for tiny_skia::Stroke {
+    fn from(stroke_style: &StrokeStyle) -> Self {
+        let dash = stroke_style.dashes().and_then(|dashes| {
+            tiny_skia::StrokeDash::new(
+                dashes.map(|length| length.max(0.0)).collect(),
+                stroke_style.dash_offset,
+            )
+        });
+        let StrokeStyle { width, join, start, end: _, miter_limit, .. } = *stroke_style;
+
+        // tiny-skia only supports a single cap for both ends, so strokes with
+        // different caps aren't drawn with this
+        tiny_skia::Stroke {
+            width,
+            miter_limit,
+            line_cap: match start {
+                Cap::Butt => tiny_skia::LineCap::Butt,
+                Cap::Square => tiny_skia::LineCap::Square,
+                Cap::Round => tiny_skia::LineCap::Round,
+            },
+            line_join: match join {
+                Join::Bevel => tiny_skia::LineJoin::Bevel,
+                Join::Miter => tiny_skia::LineJoin::Miter,
+                Join::Round => tiny_skia::LineJoin::Round,
+            },
+            dash,
+        }
+    }
+}
+
+impl From<Extend> for SpreadMode {
+    fn from(extend: Extend) -> Self {
+        match extend {
+            Extend::Pad => SpreadMode::Pad,
+            Extend::Repeat => SpreadMode::Repeat,
+            Extend::Reflect => SpreadMode::Reflect,
+        }
+    }
+}
+
+impl From<ImageQuality> for FilterQuality {
+    fn from(image_quality: ImageQuality) -> Self {
+        match image_quality {
+            ImageQuality::Low => FilterQuality::Nearest,
+            ImageQuality::Medium => FilterQuality::Bilinear,
+            ImageQuality::High => FilterQuality::Bicubic,
+        }
+    }
+}
+
+impl From<BlendMode> for tiny_skia::BlendMode {
+    fn from(blend_mode: BlendMode) -> Self {
+        use tiny_skia::BlendMode as B;
+
+        let BlendMode { mix, compose } = blend_mode;
+
+        // tiny-skia can't combine mixing and composition, so mixing takes priority
+        match mix {
+            Mix::Normal | Mix::Clip => match compose {
+                Compose::Clear => B::Clear,
+                Compose::Copy => B::Source,
+                Compose::Dest => B::Destination,
+                Compose::SrcOver => B::SourceOver,
+                Compose::DestOver => B::DestinationOver,
+                Compose::SrcIn => B::SourceIn,
+                Compose::DestIn => B::DestinationIn,
+                Compose::SrcOut => B::SourceOut,
+                Compose::DestOut => B::DestinationOut,
+                Compose::SrcAtop => B::SourceAtop,
+                Compose::DestAtop => B::DestinationAtop,
+                Compose::Xor => B::Xor,
+                Compose::Plus | Compose::PlusLighter => B::Plus,
+            },
+            Mix::Multiply => B::Multiply,
+            Mix::Screen => B::Screen,
+            Mix::Overlay => B::Overlay,
+            Mix::Darken => B::Darken,
+            Mix::Lighten => B::Lighten,
+            Mix::ColorDodge => B::ColorDodge,
+            Mix::ColorBurn => B::ColorBurn,
+            Mix::HardLight => B::HardLight,
+            Mix::SoftLight => B::SoftLight,
+            Mix::Difference => B::Difference,
+            Mix::Exclusion => B::Exclusion,
+            Mix::Hue => B::Hue,
+            Mix::Saturation => B::Saturation,
+            Mix::Color => B::Color,
+            Mix::Luminosity => B::Luminosity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(scene: &Scene) -> Image {
+        render_sized(scene, Size2::new(8, 8))
+    }
+
+    fn render_sized(scene: &Scene, size: Size2<u32>) -> Image {
+        let mut renderer = CpuRenderer::new();
+
+        renderer
+            .prepare(&mut TextContext::new(Default::default()), scene, Affine2::IDENTITY)
+            .unwrap();
+        renderer.render_to_image(size, &CpuRenderDescriptor { clear_color: Color::WHITE })
+    }
+
+    fn mixed_caps() -> Scene {
+        let mut scene = Scene::new();
+
+        scene.stroke(
+            crate::math::Line::new(Point2::new(8.0, 16.0), Point2::new(24.0, 16.0)),
+            Color::RED,
+            StrokeStyle { start: Cap::Square, end: Cap::Butt, ..StrokeStyle::new(8.0) },
+        );
+
+        scene
+    }
+
+    fn pixel(image: &Image, x: usize, y: usize) -> [u8; 4] {
+        let idx = (y * image.width() as usize + x) * 4;
+
+        image.data()[idx..idx + 4].try_into().unwrap()
+    }
+
+    fn image_extends() -> Scene {
+        let mut scene = Scene::new();
+        #[rustfmt::skip]
+        let image = Image::new(
+            vec![
+                255, 0, 0, 255,  0, 255, 0, 255,
+                0, 0, 255, 255,  0, 0, 0, 255,
+            ],
+            2,
+            2,
+        )
+        .unwrap();
+
+        scene.fill(
+            Rect::from_size(Size2::splat(8.0)),
+            ImageBrush::new(image)
+                .with_extend_x(Extend::Repeat)
+                .with_extend_y(Extend::Pad)
+                .with_quality(ImageQuality::Low),
+            FillStyle::default(),
+        );
+
+        scene
+    }
+
+    #[test]
+    fn fills_are_rasterized() {
+        let mut scene = Scene::new();
+
+        scene.fill(
+            Rect::new(Point2::new(2.0, 2.0), Size2::new(4.0, 4.0)),
+            Color::RED,
+            FillStyle::default(),
+        );
+
+        let image = render(&scene);
+
+        assert_eq!(pixel(&image, 0, 0), [255, 255, 255, 255]);
+        assert_eq!(pixel(&image, 3, 3), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn layers_are_transformed_and_clipped() {
+        let mut scene = Scene::new();
+
+        scene.with_layer(
+            Layer {
+                transform: Affine2::from_translation(Vec2::new(4.0, 0.0)),
+                blend_mode: Default::default(),
+                clip: Some(Rect::from_size(Size2::new(2.0, 8.0)).into()),
+                alpha: 1.0,
+            },
+            |scene| {
+                scene.fill(Rect::from_size(Size2::splat(8.0)), Color::BLUE, FillStyle::default())
+            },
+        );
+
+        let image = render(&scene);
+
+        assert_eq!(pixel(&image, 1, 1), [255, 255, 255, 255]);
+        assert_eq!(pixel(&image, 5, 1), [0, 0, 255, 255]);
+        assert_eq!(pixel(&image, 7, 1), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn gradients_are_shaded_within_the_path() {
+        let mut scene = Scene::new();
+
+        scene.fill(
+            Rect::new(Point2::new(4.0, 0.0), Size2::new(4.0, 8.0)),
+            SweepGradient {
+                center: Point2::new(6.0, 4.0),
+                start_angle: 0.0,
+                end_angle: TAU,
+                extend: Extend::Pad,
+                stops: vec![
+                    ColorStop { offset: 0.0, color: Color::RED },
+                    ColorStop { offset: 0.5, color: Color::BLUE },
+                    ColorStop { offset: 1.0, color: Color::RED },
+                ],
+            },
+            FillStyle::default(),
+        );
+
+        let image = render(&scene);
+        let [left_r, _, left_b, _] = pixel(&image, 4, 4);
+        let [right_r, _, right_b, _] = pixel(&image, 7, 4);
+
+        assert_eq!(pixel(&image, 2, 4), [255, 255, 255, 255]);
+        assert!(left_b > left_r);
+        assert!(right_r > right_b);
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn scenes_are_encoded_as_png() {
+        let mut scene = Scene::new();
+
+        scene.fill(Rect::from_size(Size2::splat(8.0)), Color::RED, FillStyle::default());
+
+        let png = scene
+            .render_to_png(
+                &mut TextContext::new(Default::default()),
+                Size2::new(8, 8),
+                &Default::default(),
+                &Default::default(),
+            )
+            .unwrap();
+
+        assert!(png.starts_with(b"\x89PNG"));
+
+        scene.push_layer(Layer {
+            transform: Affine2::IDENTITY,
+            blend_mode: Default::default(),
+            clip: None,
+            alpha: 1.0,
+        });
+
+        assert!(matches!(
+            scene.render_to_png(
+                &mut TextContext::new(Default::default()),
+                Size2::new(8, 8),
+                &Default::default(),
+                &Default::default(),
+            ),
+            Err(CpuRendererError::Scene(SceneError::UnclosedLayers(1))),
+        ));
+    }
+
+    #[test]
+    fn image_brushes_extend_per_axis() {
+        let image = render(&image_extends());
+
+        assert_eq!(pixel(&image, 4, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&image, 5, 0), [0, 255, 0, 255]);
+        assert_eq!(pixel(&image, 6, 6), [0, 0, 255, 255]);
+        assert_eq!(pixel(&image, 7, 6), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn strokes_have_both_caps() {
+        let image = render_sized(&mixed_caps(), Size2::splat(32));
+
+        assert_eq!(pixel(&image, 5, 16), [255, 0, 0, 255]);
+        assert_eq!(pixel(&image, 26, 16), [255, 255, 255, 255]);
+    }
+
+    /// Asserts that the GPU renderer draws a scene about the same, if there's
+    /// a GPU.
+    #[cfg(feature = "renderer")]
+    fn assert_matches_gpu(scene: &Scene, size: Size2<u32>) {
+        use crate::{wgpu, RenderDescriptor, Renderer, RendererDescriptor};
+
+        let instance = wgpu::Instance::default();
+        let Some(adapter) = pollster::block_on(instance.request_adapter(&Default::default()))
+        else {
+            // there's no GPU to compare with
+            return;
+        };
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&Default::default(), None)).unwrap();
+
+        let mut renderer = Renderer::new(
+            &device,
+            RendererDescriptor {
+                surface_format: None,
+                antialiasing_support: crate::AaSupport::area_only(),
+            },
+        )
+        .unwrap();
+
+        renderer
+            .prepare(&mut TextContext::new(Default::default()), scene, Affine2::IDENTITY)
+            .unwrap();
+
+        let gpu = renderer
+            .render_to_image(
+                &device,
+                &queue,
+                size,
+                &RenderDescriptor {
+                    antialiasing_method: crate::AaConfig::Area,
+                    clear_color: Color::WHITE,
+                },
+            )
+            .unwrap();
+        let cpu = render_sized(scene, size);
+
+        // anti-aliasing differs slightly at the edges
+        for (gpu, cpu) in gpu.data().iter().zip(cpu.data()) {
+            assert!(gpu.abs_diff(*cpu) <= 16, "{gpu} != {cpu}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "renderer")]
+    fn mixed_caps_match_the_gpu_renderer() {
+        assert_matches_gpu(&mixed_caps(), Size2::splat(32));
+    }
+
+    #[test]
+    #[cfg(feature = "renderer")]
+    fn image_extends_match_the_gpu_renderer() {
+        assert_matches_gpu(&image_extends(), Size2::splat(8));
+    }
+}
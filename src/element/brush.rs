@@ -1,10 +1,15 @@
 #[cfg(feature = "renderer")]
 use vello::peniko;
-#[cfg(feature = "renderer")]
-pub use vello::peniko::Extend;
 
-use super::Color;
-use crate::math::{Point2, Zero};
+use super::{Color, Image, ImageQuality};
+use crate::math::{Affine2, Point2, Zero};
+#[cfg(any(feature = "renderer", feature = "cpu"))]
+use crate::math::{Rect, Vec2};
+
+/// The most pixels on each axis of an image created by
+/// [`ImageBrush::extend_over`], which bounds its memory use.
+#[cfg(any(feature = "renderer", feature = "cpu"))]
+const MAX_EXTENDED_SIZE: i64 = 4096;
 
 /// Source of pixels for a fill or stroke operation.
 #[derive(Debug, Clone, PartialEq)]
@@ -13,6 +18,12 @@ pub enum Brush {
     Solid(Color),
     // Fill with a linear gradient.
     LinearGradient(LinearGradient),
+    /// Fill with a two-point conical gradient.
+    RadialGradient(RadialGradient),
+    /// Fill with a sweep (conic) gradient.
+    SweepGradient(SweepGradient),
+    /// Fill with an image.
+    Image(ImageBrush),
 }
 
 /// A linear gradient.
@@ -28,6 +39,72 @@ pub struct LinearGradient {
     pub stops: Vec<ColorStop>,
 }
 
+/// A two-point conical gradient.
+///
+/// Transitions between a start circle and an end circle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialGradient {
+    /// The center of the start circle.
+    pub start_center: Point2<f32>,
+    /// The radius of the start circle.
+    pub start_radius: f32,
+    /// The center of the end circle.
+    pub end_center: Point2<f32>,
+    /// The radius of the end circle.
+    pub end_radius: f32,
+    /// How to extend the gradient to fit the painting area.
+    pub extend: Extend,
+    /// The colors in the gradient.
+    pub stops: Vec<ColorStop>,
+}
+
+/// A sweep (conic) gradient.
+///
+/// Transitions between colors rotating around a center point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepGradient {
+    /// The center of the gradient.
+    pub center: Point2<f32>,
+    /// The start angle in radians, clockwise from the `x`-axis (`y`-down).
+    pub start_angle: f32,
+    /// The end angle in radians, clockwise from the `x`-axis (`y`-down).
+    pub end_angle: f32,
+    /// How to extend the gradient to fit the painting area.
+    pub extend: Extend,
+    /// The colors in the gradient.
+    pub stops: Vec<ColorStop>,
+}
+
+/// An image used as a brush.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageBrush {
+    /// The image to paint with.
+    pub image: Image,
+    /// The transform applied to the image (default: [`Affine2::IDENTITY`]).
+    pub transform: Affine2<f32>,
+    /// How to extend the image horizontally.
+    pub extend_x: Extend,
+    /// How to extend the image vertically.
+    pub extend_y: Extend,
+    /// How the image is sampled.
+    ///
+    /// The GPU renderer samples bilinearly, and approximates
+    /// [`ImageQuality::Low`] by scaling the image up first.
+    pub quality: ImageQuality,
+}
+
+/// Describes how a gradient is extended past its start and end.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Extend {
+    /// The edge colors are extended.
+    #[default]
+    Pad,
+    /// The gradient is repeated.
+    Repeat,
+    /// The gradient is repeated, alternating direction each time.
+    Reflect,
+}
+
 /// A color stop in a gradient.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ColorStop {
@@ -49,6 +126,30 @@ impl From<LinearGradient> for Brush {
     }
 }
 
+impl From<RadialGradient> for Brush {
+    fn from(radial_gradient: RadialGradient) -> Self {
+        Self::RadialGradient(radial_gradient)
+    }
+}
+
+impl From<SweepGradient> for Brush {
+    fn from(sweep_gradient: SweepGradient) -> Self {
+        Self::SweepGradient(sweep_gradient)
+    }
+}
+
+impl From<ImageBrush> for Brush {
+    fn from(image_brush: ImageBrush) -> Self {
+        Self::Image(image_brush)
+    }
+}
+
+impl From<Image> for Brush {
+    fn from(image: Image) -> Self {
+        Self::Image(ImageBrush::new(image))
+    }
+}
+
 #[cfg(feature = "renderer")]
 impl From<Brush> for peniko::Brush {
     fn from(brush: Brush) -> Self {
@@ -57,10 +158,45 @@ impl From<Brush> for peniko::Brush {
             Brush::LinearGradient(LinearGradient { start, end, extend, stops }) => {
                 peniko::Brush::Gradient(peniko::Gradient {
                     kind: peniko::GradientKind::Linear { start: start.into(), end: end.into() },
-                    extend,
+                    extend: extend.into(),
                     stops: peniko::ColorStops::from_iter(stops.into_iter().map(Into::into)),
                 })
             },
+            Brush::RadialGradient(RadialGradient {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+                extend,
+                stops,
+            }) => peniko::Brush::Gradient(peniko::Gradient {
+                kind: peniko::GradientKind::Radial {
+                    start_center: start_center.into(),
+                    start_radius,
+                    end_center: end_center.into(),
+                    end_radius,
+                },
+                extend: extend.into(),
+                stops: peniko::ColorStops::from_iter(stops.into_iter().map(Into::into)),
+            }),
+            Brush::SweepGradient(SweepGradient {
+                center,
+                start_angle,
+                end_angle,
+                extend,
+                stops,
+            }) => peniko::Brush::Gradient(peniko::Gradient {
+                kind: peniko::GradientKind::Sweep { center: center.into(), start_angle, end_angle },
+                extend: extend.into(),
+                stops: peniko::ColorStops::from_iter(stops.into_iter().map(Into::into)),
+            }),
+            // the transform is applied separately as the brush transform. peniko 0.1 has
+            // a single extend mode and no sampling quality, and vello ignores the extend
+            // mode, so the renderer extends and scales images itself (see
+            // `ImageBrush::extend_over`)
+            Brush::Image(ImageBrush { image, transform: _, extend_x, extend_y: _, quality: _ }) => {
+                peniko::Brush::Image(peniko::Image::from(&image).with_extend(extend_x.into()))
+            },
         }
     }
 }
@@ -76,6 +212,162 @@ impl Default for LinearGradient {
     }
 }
 
+impl Default for RadialGradient {
+    fn default() -> Self {
+        Self {
+            start_center: Point2::ZERO,
+            start_radius: 0.0,
+            end_center: Point2::ZERO,
+            end_radius: 0.0,
+            extend: Default::default(),
+            stops: Default::default(),
+        }
+    }
+}
+
+impl Default for SweepGradient {
+    fn default() -> Self {
+        Self {
+            center: Point2::ZERO,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::TAU,
+            extend: Default::default(),
+            stops: Default::default(),
+        }
+    }
+}
+
+impl ImageBrush {
+    /// Creates a new image brush with an identity transform.
+    pub fn new(image: Image) -> Self {
+        Self {
+            image,
+            transform: Affine2::IDENTITY,
+            extend_x: Default::default(),
+            extend_y: Default::default(),
+            quality: Default::default(),
+        }
+    }
+
+    /// Returns this brush with a new transform.
+    pub fn with_transform(self, transform: Affine2<f32>) -> Self {
+        Self { transform, ..self }
+    }
+
+    /// Returns this brush with the same extend mode on both axes.
+    pub fn with_extend(self, extend: Extend) -> Self {
+        Self { extend_x: extend, extend_y: extend, ..self }
+    }
+
+    /// Returns this brush with a new horizontal extend mode.
+    pub fn with_extend_x(self, extend_x: Extend) -> Self {
+        Self { extend_x, ..self }
+    }
+
+    /// Returns this brush with a new vertical extend mode.
+    pub fn with_extend_y(self, extend_y: Extend) -> Self {
+        Self { extend_y, ..self }
+    }
+
+    /// Returns this brush with a new sampling quality.
+    pub fn with_quality(self, quality: ImageQuality) -> Self {
+        Self { quality, ..self }
+    }
+
+    /// Returns a brush that paints the same within `bounds`, for backends that
+    /// can't extend images themselves.
+    ///
+    /// Its image is this one extended over `bounds`, with each pixel repeated
+    /// `scale` times on both axes, and it's padded on both axes. The image is
+    /// cut off at `MAX_EXTENDED_SIZE` pixels on each axis. Returns `None` if
+    /// `bounds` are within the image and `scale` is `1`, so this brush can
+    /// be used as is.
+    #[cfg(any(feature = "renderer", feature = "cpu"))]
+    pub(crate) fn extend_over(&self, bounds: Rect<f32>, scale: u32) -> Option<ImageBrush> {
+        let (width, height) = (self.image.width() as i64, self.image.height() as i64);
+        let area = self.transform.inverse()?.transform_rect_bbox(bounds);
+        // keeps non-finite bounds from overflowing
+        let to_int = |v: f32| v.clamp(-(1 << 30) as f32, (1 << 30) as f32) as i64;
+        let [left, top] = [area.left(), area.top()].map(|v| to_int(v.floor()));
+        let [right, bottom] = [area.right(), area.bottom()].map(|v| to_int(v.ceil()));
+
+        if width == 0 || height == 0 || right <= left || bottom <= top {
+            return None;
+        }
+
+        if scale <= 1 && left >= 0 && top >= 0 && right <= width && bottom <= height {
+            return None;
+        }
+
+        // sampling reads neighboring pixels, so they're included too
+        let [left, top, right, bottom] = [left - 2, top - 2, right + 2, bottom + 2];
+        let scale =
+            (scale as i64).clamp(1, (MAX_EXTENDED_SIZE / (right - left).max(bottom - top)).max(1));
+        let extended_width = ((right - left) * scale).min(MAX_EXTENDED_SIZE);
+        let extended_height = ((bottom - top) * scale).min(MAX_EXTENDED_SIZE);
+
+        let data = self.image.data();
+        let mut extended =
+            Vec::with_capacity(extended_width as usize * extended_height as usize * 4);
+
+        for y in 0..extended_height {
+            let row = extend_index(self.extend_y, top + y / scale, height) * width;
+
+            for x in 0..extended_width {
+                let idx = (row + extend_index(self.extend_x, left + x / scale, width)) as usize * 4;
+
+                extended.extend_from_slice(&data[idx..idx + 4]);
+            }
+        }
+
+        let image = Image::new(extended, extended_width as u32, extended_height as u32)
+            .expect("data is the size of the image")
+            .with_premultiplied(self.image.is_premultiplied());
+        let transform = self
+            .transform
+            .pre_translate(Vec2::new(left as f32, top as f32))
+            .pre_scale(Vec2::splat(1.0 / scale as f32));
+
+        Some(ImageBrush {
+            image,
+            transform,
+            extend_x: Extend::Pad,
+            extend_y: Extend::Pad,
+            quality: self.quality,
+        })
+    }
+}
+
+/// Returns the index of the pixel an extend mode maps an index on an axis of
+/// some length to.
+#[cfg(any(feature = "renderer", feature = "cpu"))]
+fn extend_index(extend: Extend, index: i64, len: i64) -> i64 {
+    match extend {
+        Extend::Pad => index.clamp(0, len - 1),
+        Extend::Repeat => index.rem_euclid(len),
+        Extend::Reflect => {
+            let index = index.rem_euclid(2 * len);
+
+            if index < len {
+                index
+            } else {
+                2 * len - 1 - index
+            }
+        },
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl From<Extend> for peniko::Extend {
+    fn from(extend: Extend) -> Self {
+        match extend {
+            Extend::Pad => peniko::Extend::Pad,
+            Extend::Repeat => peniko::Extend::Repeat,
+            Extend::Reflect => peniko::Extend::Reflect,
+        }
+    }
+}
+
 impl ColorStop {
     /// Creates a new color stop from its offset and color.
     pub const fn new(offset: f32, color: Color) -> Self {
@@ -26,8 +26,8 @@ impl Color {
     }
 }
 
-#[cfg(feature = "renderer")]
-impl From<Color> for vello::peniko::Color {
+#[cfg(feature = "text")]
+impl From<Color> for peniko::Color {
     fn from(color: Color) -> Self {
         let Color { r, g, b, a } = color;
 
@@ -13,6 +13,16 @@ pub enum FillRule {
     EvenOdd,
 }
 
+impl FillRule {
+    /// Returns `true` if a point with a winding number is inside a shape.
+    pub fn is_filled(self, winding: i32) -> bool {
+        match self {
+            Self::NonZero => winding != 0,
+            Self::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
 #[cfg(feature = "renderer")]
 impl From<FillRule> for vello::peniko::Fill {
     fn from(fill_rule: FillRule) -> Self {
@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+#[cfg(feature = "png")]
+pub use self::png::*;
+
+#[cfg(feature = "png")]
+mod png;
+
+/// A bitmap image with 8-bit `rgba` channels.
+///
+/// The pixel data is reference-counted, so images are cheap to clone.
+#[derive(Clone, PartialEq)]
+pub struct Image {
+    data: Arc<[u8]>,
+    width: u32,
+    height: u32,
+    premultiplied: bool,
+}
+
+/// How an image is sampled when it is scaled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageQuality {
+    /// Nearest-neighbor sampling.
+    Low,
+    /// Bilinear sampling.
+    #[default]
+    Medium,
+    /// Bicubic sampling.
+    High,
+}
+
+/// Error when creating an [`Image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum ImageError {
+    /// The length of the pixel data doesn't match the dimensions of the image.
+    #[error("expected {expected} bytes of pixel data, found {found}")]
+    InvalidLength { expected: usize, found: usize },
+}
+
+impl Image {
+    /// Creates an image from tightly packed `rgba` pixel data with straight
+    /// (not premultiplied) alpha.
+    ///
+    /// Returns an error if the length of `data` isn't `width * height * 4`.
+    pub fn new(data: impl Into<Arc<[u8]>>, width: u32, height: u32) -> Result<Self, ImageError> {
+        let data = data.into();
+        let expected = width as usize * height as usize * 4;
+
+        if data.len() != expected {
+            return Err(ImageError::InvalidLength { expected, found: data.len() });
+        }
+
+        Ok(Self { data, width, height, premultiplied: false })
+    }
+
+    /// Returns this image with the pixel data marked as having premultiplied
+    /// alpha or not.
+    pub fn with_premultiplied(self, premultiplied: bool) -> Self {
+        Self { premultiplied, ..self }
+    }
+
+    /// The pixel data of this image.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The width of this image in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of this image in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns `true` if the pixel data has premultiplied alpha.
+    pub fn is_premultiplied(&self) -> bool {
+        self.premultiplied
+    }
+
+    /// Returns the pixel data of this image with straight alpha.
+    ///
+    /// Only allocates if the image is premultiplied.
+    pub fn straight_data(&self) -> Cow<'_, [u8]> {
+        if !self.premultiplied {
+            return Cow::Borrowed(&self.data);
+        }
+
+        let mut data = self.data.to_vec();
+
+        for pixel in data.chunks_exact_mut(4) {
+            let a = pixel[3] as u16;
+
+            for channel in &mut pixel[..3] {
+                // fully transparent pixels are left as-is
+                if let Some(straight) = (*channel as u16 * 255 + a / 2).checked_div(a) {
+                    *channel = straight.min(255) as u8;
+                }
+            }
+        }
+
+        Cow::Owned(data)
+    }
+}
+
+impl fmt::Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("premultiplied", &self.premultiplied)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl From<&Image> for vello::peniko::Image {
+    fn from(image: &Image) -> Self {
+        use vello::peniko;
+
+        // vello expects straight alpha
+        let data = if image.premultiplied {
+            peniko::Blob::from(image.straight_data().into_owned())
+        } else {
+            peniko::Blob::new(Arc::new(image.data.clone()))
+        };
+
+        peniko::Image::new(data, peniko::Format::Rgba8, image.width, image.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_length_is_validated() {
+        assert!(Image::new(vec![0; 16], 2, 2).is_ok());
+        assert_eq!(
+            Image::new(vec![0; 15], 2, 2),
+            Err(ImageError::InvalidLength { expected: 16, found: 15 }),
+        );
+    }
+
+    #[test]
+    fn premultiplied_data_is_unpremultiplied() {
+        let image =
+            Image::new(vec![64, 32, 0, 128, 10, 20, 30, 0], 2, 1).unwrap().with_premultiplied(true);
+
+        assert_eq!(&*image.straight_data(), &[128, 64, 0, 128, 10, 20, 30, 0]);
+    }
+}
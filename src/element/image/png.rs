@@ -0,0 +1,130 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use thiserror::Error;
+
+use super::Image;
+
+/// Parameters for encoding an [`Image`] as PNG.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PngDescriptor {
+    /// The color type of the encoded image (default: [`PngColorType::Rgba`]).
+    pub color_type: PngColorType,
+    /// The resolution of the image in dots-per-inch.
+    ///
+    /// If `None`, no physical dimensions are written.
+    pub dpi: Option<f32>,
+}
+
+/// The color type of an encoded PNG.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PngColorType {
+    /// 8-bit `rgba`.
+    #[default]
+    Rgba,
+    /// 8-bit `rgb`.
+    ///
+    /// The alpha channel is discarded.
+    Rgb,
+}
+
+/// Error when encoding an [`Image`] as PNG.
+#[derive(Debug, Error)]
+pub enum PngError {
+    #[error(transparent)]
+    Encoding(#[from] ::png::EncodingError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl Image {
+    /// Encodes this image as PNG.
+    pub fn encode_png(&self, desc: &PngDescriptor) -> Result<Vec<u8>, PngError> {
+        let mut bytes = Vec::new();
+
+        self.write_png(&mut bytes, desc)?;
+
+        Ok(bytes)
+    }
+
+    /// Encodes this image as PNG and saves it to a file.
+    pub fn save_png(&self, path: impl AsRef<Path>, desc: &PngDescriptor) -> Result<(), PngError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        self.write_png(&mut writer, desc)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Encodes this image as PNG into a writer.
+    ///
+    /// Premultiplied images are converted to straight alpha.
+    pub fn write_png(&self, writer: impl Write, desc: &PngDescriptor) -> Result<(), PngError> {
+        let PngDescriptor { color_type, dpi } = *desc;
+
+        let mut encoder = ::png::Encoder::new(writer, self.width, self.height);
+
+        encoder.set_depth(::png::BitDepth::Eight);
+        encoder.set_color(color_type.into());
+
+        if let Some(dpi) = dpi {
+            // pixels per inch -> pixels per meter
+            let ppu = (dpi / 0.0254).round() as u32;
+
+            encoder.set_pixel_dims(Some(::png::PixelDimensions {
+                xppu: ppu,
+                yppu: ppu,
+                unit: ::png::Unit::Meter,
+            }));
+        }
+
+        let data = self.straight_data();
+        let mut writer = encoder.write_header()?;
+
+        match color_type {
+            PngColorType::Rgba => writer.write_image_data(&data)?,
+            PngColorType::Rgb => {
+                let data: Vec<u8> =
+                    data.chunks_exact(4).flat_map(|pixel| [pixel[0], pixel[1], pixel[2]]).collect();
+
+                writer.write_image_data(&data)?;
+            },
+        }
+
+        writer.finish()?;
+
+        Ok(())
+    }
+}
+
+impl From<PngColorType> for ::png::ColorType {
+    fn from(color_type: PngColorType) -> Self {
+        match color_type {
+            PngColorType::Rgba => ::png::ColorType::Rgba,
+            PngColorType::Rgb => ::png::ColorType::Rgb,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_png_round_trips() {
+        let image = Image::new(vec![255, 0, 0, 255, 0, 0, 255, 128], 2, 1).unwrap();
+        let bytes = image
+            .encode_png(&PngDescriptor { color_type: PngColorType::Rgb, dpi: Some(144.0) })
+            .unwrap();
+
+        let mut reader = ::png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).unwrap();
+
+        assert_eq!(info.color_type, ::png::ColorType::Rgb);
+        assert_eq!(&data[..info.buffer_size()], &[255, 0, 0, 0, 0, 255]);
+        assert_eq!(reader.info().pixel_dims.map(|dims| dims.xppu), Some(5669));
+    }
+}
@@ -1,12 +1,11 @@
-pub use vello::peniko::{BlendMode, Compose, Mix};
-
 use super::Path;
 use crate::math::Affine2;
 
 /// Describes a layer in a [`Scene`](crate::Scene).
 #[derive(Debug, Clone, PartialEq)]
 pub struct Layer {
-    /// The transform applied to the layer.
+    /// The transform applied to the contents and clip of the layer, within
+    /// the transforms of its parents.
     pub transform: Affine2<f32>,
     /// How to blend layers.
     pub blend_mode: BlendMode,
@@ -15,3 +14,177 @@ pub struct Layer {
     /// The opacity of the layer (default: `1.0`).
     pub alpha: f32,
 }
+
+/// Describes how a layer is blended with what is beneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlendMode {
+    /// How colors are mixed.
+    pub mix: Mix,
+    /// How the layer is composited.
+    pub compose: Compose,
+}
+
+/// The color mixing function of a [`BlendMode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mix {
+    /// Selects the source color.
+    Normal,
+    /// Multiplies the source and destination colors.
+    Multiply,
+    /// Multiplies the complements of the source and destination colors, then
+    /// complements the result.
+    Screen,
+    /// Multiplies or screens depending on the destination color.
+    Overlay,
+    /// Selects the darker of the source and destination colors.
+    Darken,
+    /// Selects the lighter of the source and destination colors.
+    Lighten,
+    /// Brightens the destination color to reflect the source color.
+    ColorDodge,
+    /// Darkens the destination color to reflect the source color.
+    ColorBurn,
+    /// Multiplies or screens depending on the source color.
+    HardLight,
+    /// Darkens or lightens depending on the source color.
+    SoftLight,
+    /// Subtracts the darker color from the lighter color.
+    Difference,
+    /// Like [`Mix::Difference`], but with lower contrast.
+    Exclusion,
+    /// The hue of the source with the saturation and luminosity of the
+    /// destination.
+    Hue,
+    /// The saturation of the source with the hue and luminosity of the
+    /// destination.
+    Saturation,
+    /// The hue and saturation of the source with the luminosity of the
+    /// destination.
+    Color,
+    /// The luminosity of the source with the hue and saturation of the
+    /// destination.
+    Luminosity,
+    /// Same as [`Mix::Normal`], but doesn't require an isolated blend group.
+    #[default]
+    Clip,
+}
+
+/// The composition function of a [`BlendMode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compose {
+    /// Neither the source nor destination are present.
+    Clear,
+    /// Only the source is present.
+    Copy,
+    /// Only the destination is present.
+    Dest,
+    /// The source is placed over the destination.
+    #[default]
+    SrcOver,
+    /// The destination is placed over the source.
+    DestOver,
+    /// The parts of the source that overlap the destination.
+    SrcIn,
+    /// The parts of the destination that overlap the source.
+    DestIn,
+    /// The parts of the source outside of the destination.
+    SrcOut,
+    /// The parts of the destination outside of the source.
+    DestOut,
+    /// The source where it overlaps the destination, the destination
+    /// elsewhere.
+    SrcAtop,
+    /// The destination where it overlaps the source, the source elsewhere.
+    DestAtop,
+    /// The non-overlapping parts of the source and destination.
+    Xor,
+    /// The sum of the source and destination.
+    Plus,
+    /// Like [`Compose::Plus`], but allows cross-fading between layers.
+    PlusLighter,
+}
+
+impl BlendMode {
+    /// Creates a new blend mode from its mixing and composition functions.
+    pub const fn new(mix: Mix, compose: Compose) -> Self {
+        Self { mix, compose }
+    }
+}
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        Self::new(Mix::Clip, Compose::SrcOver)
+    }
+}
+
+impl From<Mix> for BlendMode {
+    fn from(mix: Mix) -> Self {
+        Self::new(mix, Compose::SrcOver)
+    }
+}
+
+impl From<Compose> for BlendMode {
+    fn from(compose: Compose) -> Self {
+        Self::new(Mix::Normal, compose)
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl From<BlendMode> for vello::peniko::BlendMode {
+    fn from(blend_mode: BlendMode) -> Self {
+        let BlendMode { mix, compose } = blend_mode;
+
+        vello::peniko::BlendMode::new(mix.into(), compose.into())
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl From<Mix> for vello::peniko::Mix {
+    fn from(mix: Mix) -> Self {
+        use vello::peniko;
+
+        match mix {
+            Mix::Normal => peniko::Mix::Normal,
+            Mix::Multiply => peniko::Mix::Multiply,
+            Mix::Screen => peniko::Mix::Screen,
+            Mix::Overlay => peniko::Mix::Overlay,
+            Mix::Darken => peniko::Mix::Darken,
+            Mix::Lighten => peniko::Mix::Lighten,
+            Mix::ColorDodge => peniko::Mix::ColorDodge,
+            Mix::ColorBurn => peniko::Mix::ColorBurn,
+            Mix::HardLight => peniko::Mix::HardLight,
+            Mix::SoftLight => peniko::Mix::SoftLight,
+            Mix::Difference => peniko::Mix::Difference,
+            Mix::Exclusion => peniko::Mix::Exclusion,
+            Mix::Hue => peniko::Mix::Hue,
+            Mix::Saturation => peniko::Mix::Saturation,
+            Mix::Color => peniko::Mix::Color,
+            Mix::Luminosity => peniko::Mix::Luminosity,
+            Mix::Clip => peniko::Mix::Clip,
+        }
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl From<Compose> for vello::peniko::Compose {
+    fn from(compose: Compose) -> Self {
+        use vello::peniko;
+
+        match compose {
+            Compose::Clear => peniko::Compose::Clear,
+            Compose::Copy => peniko::Compose::Copy,
+            Compose::Dest => peniko::Compose::Dest,
+            Compose::SrcOver => peniko::Compose::SrcOver,
+            Compose::DestOver => peniko::Compose::DestOver,
+            Compose::SrcIn => peniko::Compose::SrcIn,
+            Compose::DestIn => peniko::Compose::DestIn,
+            Compose::SrcOut => peniko::Compose::SrcOut,
+            Compose::DestOut => peniko::Compose::DestOut,
+            Compose::SrcAtop => peniko::Compose::SrcAtop,
+            Compose::DestAtop => peniko::Compose::DestAtop,
+            Compose::Xor => peniko::Compose::Xor,
+            Compose::Plus => peniko::Compose::Plus,
+            Compose::PlusLighter => peniko::Compose::PlusLighter,
+        }
+    }
+}
@@ -3,25 +3,27 @@
 pub use self::brush::*;
 pub use self::color::*;
 pub use self::fill::*;
+pub use self::image::*;
 pub use self::layer::*;
 pub use self::path::*;
 pub use self::source::*;
 pub use self::stroke::*;
 pub use self::text::*;
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 pub use self::text_context::*;
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 pub use self::text_layout::*;
 
 mod brush;
 mod color;
 mod fill;
+mod image;
 mod layer;
 mod path;
 mod source;
 mod stroke;
 mod text;
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 mod text_context;
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 mod text_layout;
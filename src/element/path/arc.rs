@@ -0,0 +1,151 @@
+use std::f32::consts::TAU;
+
+use super::PathBuilder;
+use crate::math::{Point2, Vec2};
+
+/// A tolerance that splits arcs into quarter turns, which are accurate to
+/// ~0.03% of the radius.
+pub(crate) const QUARTER_TURNS: f32 = f32::INFINITY;
+
+/// The most segments per turn, used as the tolerance approaches `0`.
+const MAX_SEGMENTS_PER_TURN: f32 = 1024.0;
+
+/// The most segments of an arc, however many turns it makes.
+const MAX_SEGMENTS: f32 = 65536.0;
+
+/// An elliptical arc described by its center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Arc {
+    pub center: Point2<f32>,
+    pub radii: Vec2<f32>,
+    /// The rotation of the ellipse's `x`-axis in radians.
+    pub x_rotation: f32,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+}
+
+impl Arc {
+    /// Converts an arc in the SVG endpoint parameterization.
+    ///
+    /// Returns `None` if the arc is a straight line. Radii that are too small
+    /// to reach `to` are scaled up, as described in the SVG specification.
+    pub fn from_endpoints(
+        from: Point2<f32>,
+        to: Point2<f32>,
+        radii: Vec2<f32>,
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+    ) -> Option<Self> {
+        let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+
+        if from == to || rx == 0.0 || ry == 0.0 {
+            return None;
+        }
+
+        let (sin, cos) = x_rotation.sin_cos();
+
+        // the midpoint between the endpoints in the ellipse's coordinate space
+        let (dx, dy) = ((from.x - to.x) / 2.0, (from.y - to.y) / 2.0);
+        let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+
+        if lambda > 1.0 {
+            let scale = lambda.sqrt();
+
+            rx *= scale;
+            ry *= scale;
+        }
+
+        let (rx2, ry2) = (rx * rx, ry * ry);
+        let numerator = rx2 * ry2 - rx2 * y1 * y1 - ry2 * x1 * x1;
+        let denominator = rx2 * y1 * y1 + ry2 * x1 * x1;
+        let coefficient =
+            (numerator / denominator).max(0.0).sqrt() * if large_arc == sweep { -1.0 } else { 1.0 };
+
+        let (cx1, cy1) = (coefficient * rx * y1 / ry, coefficient * -ry * x1 / rx);
+        let center = Point2::new(
+            cos * cx1 - sin * cy1 + (from.x + to.x) / 2.0,
+            sin * cx1 + cos * cy1 + (from.y + to.y) / 2.0,
+        );
+
+        let start = Vec2::new((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let end = Vec2::new((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+
+        let start_angle = start.y.atan2(start.x);
+        let mut sweep_angle = angle_between(start, end);
+
+        if !sweep && sweep_angle > 0.0 {
+            sweep_angle -= TAU;
+        } else if sweep && sweep_angle < 0.0 {
+            sweep_angle += TAU;
+        }
+
+        Some(Self { center, radii: Vec2::new(rx, ry), x_rotation, start_angle, sweep_angle })
+    }
+
+    /// Returns the point on the ellipse at an angle.
+    pub fn point(&self, angle: f32) -> Point2<f32> {
+        let (sin, cos) = angle.sin_cos();
+
+        self.center + self.rotate(Vec2::new(self.radii.x * cos, self.radii.y * sin))
+    }
+
+    /// Appends cubic segments approximating this arc to a builder.
+    ///
+    /// The arc is split into at least one segment per quarter turn, and more
+    /// if needed to stay within `tolerance`, up to [`MAX_SEGMENTS_PER_TURN`].
+    /// Nothing is appended if the sweep isn't finite. Assumes the builder is
+    /// at the start of the arc.
+    pub fn append_to(&self, builder: &mut PathBuilder, tolerance: f32) {
+        if !self.sweep_angle.is_finite() {
+            return;
+        }
+
+        // the error of a segment is proportional to the 6th power of its angle (from
+        // kurbo), and `max` maps a NaN tolerance to the finest approximation
+        let scaled_error = self.radii.x.max(self.radii.y) / tolerance.max(f32::MIN_POSITIVE);
+        let segments_per_turn =
+            (1.1163 * scaled_error).powf(1.0 / 6.0).clamp(3.999_999, MAX_SEGMENTS_PER_TURN);
+        let segments =
+            (segments_per_turn * self.sweep_angle.abs() / TAU).ceil().clamp(1.0, MAX_SEGMENTS);
+        let step = self.sweep_angle / segments;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+
+        let mut angle = self.start_angle;
+
+        for _ in 0..segments as usize {
+            let next = angle + step;
+
+            let (from, to) = (self.point(angle), self.point(next));
+            let (d0, d1) = (self.derivative(angle), self.derivative(next));
+
+            builder.cubic_to(
+                Point2::new(from.x + k * d0.x, from.y + k * d0.y),
+                Point2::new(to.x - k * d1.x, to.y - k * d1.y),
+                to,
+            );
+
+            angle = next;
+        }
+    }
+
+    /// Returns the derivative of [`Arc::point`].
+    fn derivative(&self, angle: f32) -> Vec2<f32> {
+        let (sin, cos) = angle.sin_cos();
+
+        self.rotate(Vec2::new(-self.radii.x * sin, self.radii.y * cos))
+    }
+
+    fn rotate(&self, v: Vec2<f32>) -> Vec2<f32> {
+        let (sin, cos) = self.x_rotation.sin_cos();
+
+        Vec2::new(cos * v.x - sin * v.y, sin * v.x + cos * v.y)
+    }
+}
+
+/// Returns the signed angle from `u` to `v`.
+fn angle_between(u: Vec2<f32>, v: Vec2<f32>) -> f32 {
+    (u.x * v.y - u.y * v.x).atan2(u.x * v.x + u.y * v.y)
+}
@@ -0,0 +1,494 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::{Path, PathBuilder};
+use crate::element::FillRule;
+use crate::math::Point2;
+
+impl Path {
+    /// Returns the area covered by either this path or another.
+    ///
+    /// Both paths are filled with `rule`, and their curves are flattened into
+    /// lines that deviate from them by at most `tolerance`. The result has no
+    /// overlapping subpaths, so it can be filled with either rule.
+    pub fn union(&self, other: &Path, rule: FillRule, tolerance: f32) -> Path {
+        boolean(self, other, rule, tolerance, |a, b| a || b)
+    }
+
+    /// Returns the area covered by both this path and another.
+    ///
+    /// See [`Path::union`] for how the paths are filled.
+    pub fn intersection(&self, other: &Path, rule: FillRule, tolerance: f32) -> Path {
+        boolean(self, other, rule, tolerance, |a, b| a && b)
+    }
+
+    /// Returns the area covered by this path but not another.
+    ///
+    /// See [`Path::union`] for how the paths are filled.
+    pub fn difference(&self, other: &Path, rule: FillRule, tolerance: f32) -> Path {
+        boolean(self, other, rule, tolerance, |a, b| a && !b)
+    }
+
+    /// Returns the area covered by exactly one of this path and another.
+    ///
+    /// See [`Path::union`] for how the paths are filled.
+    pub fn xor(&self, other: &Path, rule: FillRule, tolerance: f32) -> Path {
+        boolean(self, other, rule, tolerance, |a, b| a != b)
+    }
+}
+
+/// A line of a flattened operand.
+#[derive(Clone, Copy)]
+struct Edge {
+    from: [f64; 2],
+    to: [f64; 2],
+    operand: usize,
+}
+
+/// Edges of both operands between the same 2 vertices, with the sum of their
+/// directions relative to `from` -> `to` for each operand.
+struct Group {
+    from: [f64; 2],
+    to: [f64; 2],
+    winding: [i32; 2],
+}
+
+/// Computes a boolean operation by splitting the edges of both paths where
+/// they intersect, then keeping the edges with the result of `op` differing
+/// on either side.
+fn boolean(
+    a: &Path,
+    b: &Path,
+    rule: FillRule,
+    tolerance: f32,
+    op: impl Fn(bool, bool) -> bool,
+) -> Path {
+    let mut edges = Vec::new();
+
+    flatten(a, 0, tolerance, &mut edges);
+    flatten(b, 1, tolerance, &mut edges);
+
+    let groups = split(&edges, tolerance);
+    let inside = |winding: [i32; 2]| op(rule.is_filled(winding[0]), rule.is_filled(winding[1]));
+
+    // rays along `x` only cross groups in the same band of `y`, and vice versa
+    let bands = [Bands::new(&groups, 1), Bands::new(&groups, 0)];
+    let mut boundary = Vec::new();
+
+    for (i, group) in groups.iter().enumerate() {
+        let [dx, dy] = [group.to[0] - group.from[0], group.to[1] - group.from[1]];
+        let middle = [(group.from[0] + group.to[0]) / 2.0, (group.from[1] + group.to[1]) / 2.0];
+        // cast rays along the axis that crosses fewer groups, unless the edge is
+        // nearly aligned with it
+        let axis = match (dy.abs() >= dx.abs() / 4.0, dx.abs() >= dy.abs() / 4.0) {
+            (true, true) if bands[0].get(middle).len() <= bands[1].get(middle).len() => 0,
+            (true, false) => 0,
+            _ => 1,
+        };
+
+        // the winding on the far side of the edge along the ray, and on the near side,
+        // which is across the edge
+        let mut far = [0; 2];
+
+        for &j in bands[axis].get(middle) {
+            if i != j {
+                let other = &groups[j];
+                let crossing = crossing(other, middle, axis);
+
+                far[0] += crossing * other.winding[0];
+                far[1] += crossing * other.winding[1];
+            }
+        }
+
+        let direction = crossing_direction(group, axis);
+        let near = [far[0] + direction * group.winding[0], far[1] + direction * group.winding[1]];
+        let (inside_near, inside_far) = (inside(near), inside(far));
+
+        if inside_near == inside_far {
+            continue;
+        }
+
+        // the result is on the right of edges (`y`-down), so that subpaths are
+        // clockwise and holes are counter-clockwise
+        let right = [-dy, dx][axis];
+        let right_is_near = right < 0.0;
+
+        if inside_near == right_is_near {
+            boundary.push((group.from, group.to));
+        } else {
+            boundary.push((group.to, group.from));
+        }
+    }
+
+    chain(boundary)
+}
+
+/// Groups sorted into bands along an axis by the range they span.
+struct Bands {
+    axis: usize,
+    min: f64,
+    size: f64,
+    bands: Vec<Vec<usize>>,
+}
+
+impl Bands {
+    fn new(groups: &[Group], axis: usize) -> Self {
+        let range = |group: &Group| {
+            let (a, b) = (group.from[axis], group.to[axis]);
+
+            (a.min(b), a.max(b))
+        };
+        let (min, max) = groups
+            .iter()
+            .map(range)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (a, b)| {
+                (min.min(a), max.max(b))
+            });
+
+        let count = (groups.len() / 4).clamp(1, 4096);
+        let size = ((max - min) / count as f64).max(f64::MIN_POSITIVE);
+        let mut bands = vec![Vec::new(); count];
+
+        for (i, group) in groups.iter().enumerate() {
+            let (a, b) = range(group);
+            let (first, last) =
+                (((a - min) / size) as usize, (((b - min) / size) as usize).min(count - 1));
+
+            for band in &mut bands[first.min(count - 1)..=last] {
+                band.push(i);
+            }
+        }
+
+        Self { axis, min, size, bands }
+    }
+
+    /// Returns the groups that may span the coordinate of a point.
+    fn get(&self, point: [f64; 2]) -> &[usize] {
+        let index = ((point[self.axis] - self.min) / self.size).max(0.0) as usize;
+
+        self.bands.get(index.min(self.bands.len() - 1)).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Adds the edges of a path to `edges`, treating every subpath as closed.
+fn flatten(path: &Path, operand: usize, tolerance: f32, edges: &mut Vec<Edge>) {
+    for segment in path.filled_segments() {
+        let mut from = segment.start();
+
+        segment.flatten(tolerance, &mut |to| {
+            if from != to {
+                edges.push(Edge {
+                    from: [from.x as _, from.y as _],
+                    to: [to.x as _, to.y as _],
+                    operand,
+                });
+            }
+
+            from = to;
+        });
+    }
+}
+
+/// Splits edges where they intersect and groups the pieces between the same
+/// vertices.
+///
+/// Vertices are snapped to a grid much finer than `tolerance`, so that pieces
+/// meet exactly.
+fn split(edges: &[Edge], tolerance: f32) -> Vec<Group> {
+    let mut splits = vec![Vec::new(); edges.len()];
+
+    // sweep along `x`, only testing edges that overlap
+    let min_x = |edge: &Edge| edge.from[0].min(edge.to[0]);
+    let max_x = |edge: &Edge| edge.from[0].max(edge.to[0]);
+    let mut order: Vec<_> = (0..edges.len()).collect();
+
+    order.sort_by(|&i, &j| min_x(&edges[i]).total_cmp(&min_x(&edges[j])));
+
+    let grid = f64::from(tolerance).max(1e-3) * 1e-4;
+
+    for (k, &i) in order.iter().enumerate() {
+        for &j in &order[k + 1..] {
+            if min_x(&edges[j]) > max_x(&edges[i]) + grid {
+                break;
+            }
+
+            intersect(&edges[i], &edges[j], grid, |t, u| {
+                splits[i].push(t);
+                splits[j].push(u);
+            });
+        }
+    }
+
+    let snap = |[x, y]: [f64; 2]| ((x / grid).round() as i64, (y / grid).round() as i64);
+    let unsnap = |(x, y): (i64, i64)| [x as f64 * grid, y as f64 * grid];
+
+    let mut groups = BTreeMap::new();
+
+    for (edge, splits) in edges.iter().zip(&mut splits) {
+        splits.extend([0.0, 1.0]);
+        splits.sort_by(f64::total_cmp);
+
+        let vertices: Vec<_> = splits
+            .iter()
+            .map(|&t| {
+                snap([
+                    edge.from[0] + (edge.to[0] - edge.from[0]) * t,
+                    edge.from[1] + (edge.to[1] - edge.from[1]) * t,
+                ])
+            })
+            .collect();
+
+        for pair in vertices.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+
+            if from == to {
+                continue;
+            }
+
+            let (key, direction) = if from < to { ((from, to), 1) } else { ((to, from), -1) };
+            let winding: &mut [i32; 2] = groups.entry(key).or_default();
+
+            winding[edge.operand] += direction;
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, winding)| *winding != [0, 0])
+        .map(|((from, to), winding)| Group { from: unsnap(from), to: unsnap(to), winding })
+        .collect()
+}
+
+/// Calls `f` with the parameters along each edge where they intersect,
+/// including where they overlap or where the end of one is within `distance`
+/// of the other.
+fn intersect(a: &Edge, b: &Edge, distance: f64, mut f: impl FnMut(f64, f64)) {
+    let [ax, ay] = [a.to[0] - a.from[0], a.to[1] - a.from[1]];
+    let [bx, by] = [b.to[0] - b.from[0], b.to[1] - b.from[1]];
+    let [cx, cy] = [b.from[0] - a.from[0], b.from[1] - a.from[1]];
+
+    if a.from[1].max(a.to[1]) + distance < b.from[1].min(b.to[1])
+        || b.from[1].max(b.to[1]) + distance < a.from[1].min(a.to[1])
+    {
+        return;
+    }
+
+    // the parameter of the closest point on an edge, if it's within `distance`
+    let project = |[x, y]: [f64; 2], from: [f64; 2], [dx, dy]: [f64; 2]| {
+        let (px, py) = (x - from[0], y - from[1]);
+        let t = (px * dx + py * dy) / (dx * dx + dy * dy);
+        let near = (px * dy - py * dx).abs() <= distance * dx.hypot(dy);
+
+        (near && (0.0..=1.0).contains(&t)).then_some(t)
+    };
+
+    // ends near the other edge, which includes where edges overlap, and where
+    // they meet at a shallow angle
+    for (point, u) in [(b.from, 0.0), (b.to, 1.0)] {
+        if let Some(t) = project(point, a.from, [ax, ay]) {
+            f(t, u);
+        }
+    }
+
+    for (point, t) in [(a.from, 0.0), (a.to, 1.0)] {
+        if let Some(u) = project(point, b.from, [bx, by]) {
+            f(t, u);
+        }
+    }
+
+    let denominator = ax * by - ay * bx;
+
+    if denominator.abs() > 1e-12 * ax.hypot(ay) * bx.hypot(by) {
+        let t = (cx * by - cy * bx) / denominator;
+        let u = (cx * ay - cy * ax) / denominator;
+
+        // crossings at the ends are found above
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            f(t, u);
+        }
+    }
+}
+
+/// Returns the winding contribution of a group crossed by a ray from `point`
+/// towards `+x` (or `+y` for `axis == 1`).
+///
+/// Rays along `y` are mirrored, which negates every crossing consistently.
+fn crossing(group: &Group, point: [f64; 2], axis: usize) -> i32 {
+    let other = 1 - axis;
+    let (from, to) = (group.from, group.to);
+
+    // half-open, so that a ray through a vertex crosses once
+    let crosses = (from[other] <= point[other]) != (to[other] <= point[other]);
+
+    if !crosses {
+        return 0;
+    }
+
+    let t = (point[other] - from[other]) / (to[other] - from[other]);
+    let position = from[axis] + (to[axis] - from[axis]) * t;
+
+    if position > point[axis] {
+        crossing_direction(group, axis)
+    } else {
+        0
+    }
+}
+
+fn crossing_direction(group: &Group, axis: usize) -> i32 {
+    let other = 1 - axis;
+
+    if group.to[other] > group.from[other] {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Joins directed edges into closed subpaths.
+fn chain(edges: Vec<([f64; 2], [f64; 2])>) -> Path {
+    let key = |[x, y]: [f64; 2]| (x.to_bits(), y.to_bits());
+    let mut outgoing: HashMap<_, Vec<usize>> = HashMap::new();
+
+    for (i, (from, _)) in edges.iter().enumerate() {
+        outgoing.entry(key(*from)).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut builder = PathBuilder::new();
+
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+
+        let start = edges[first].0;
+        let mut points = vec![start];
+        let mut current = first;
+
+        loop {
+            used[current] = true;
+
+            let to = edges[current].1;
+
+            if to == start {
+                break;
+            }
+
+            points.push(to);
+
+            let next = outgoing
+                .get(&key(to))
+                .and_then(|candidates| candidates.iter().copied().find(|&i| !used[i]));
+
+            match next {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+
+        let points = remove_collinear(points);
+
+        if points.len() < 3 {
+            continue;
+        }
+
+        builder.open(Point2::new(points[0][0] as f32, points[0][1] as f32));
+
+        for point in &points[1..] {
+            builder.line_to(Point2::new(point[0] as f32, point[1] as f32));
+        }
+
+        builder.close();
+    }
+
+    builder.build()
+}
+
+/// Removes the points of a closed polygon that are between 2 collinear edges
+/// going the same way.
+fn remove_collinear(points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    let len = points.len();
+
+    (0..len)
+        .filter(|&i| {
+            let [previous, point, next] =
+                [points[(i + len - 1) % len], points[i], points[(i + 1) % len]];
+            let (a, b) = (
+                [point[0] - previous[0], point[1] - previous[1]],
+                [next[0] - point[0], next[1] - point[1]],
+            );
+            let cross = a[0] * b[1] - a[1] * b[0];
+            let dot = a[0] * b[0] + a[1] * b[1];
+
+            cross.abs() > 1e-12 * a[0].hypot(a[1]) * b[0].hypot(b[1]) || dot < 0.0
+        })
+        .map(|i| points[i])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{Circle, Rect, Size2};
+
+    fn square(x: f32, y: f32, size: f32) -> Path {
+        Path::from(Rect::new(Point2::new(x, y), Size2::splat(size)))
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let (a, b) = (square(0.0, 0.0, 2.0), square(1.0, 1.0, 2.0));
+        let rule = FillRule::NonZero;
+
+        assert_eq!(a.union(&b, rule, 0.1).area(), 7.0);
+        assert_eq!(a.intersection(&b, rule, 0.1).area(), 1.0);
+        assert_eq!(a.difference(&b, rule, 0.1).area(), 3.0);
+        assert_eq!(a.xor(&b, rule, 0.1).area(), 6.0);
+        // an L-shape has 6 corners
+        assert_eq!(a.difference(&b, rule, 0.1).len(), 7);
+        assert!(a.intersection(&square(5.0, 5.0, 1.0), rule, 0.1).is_empty());
+    }
+
+    #[test]
+    fn shared_edges_are_merged() {
+        let union = square(0.0, 0.0, 1.0).union(&square(1.0, 0.0, 1.0), FillRule::NonZero, 0.1);
+
+        assert_eq!(union.area(), 2.0);
+        assert_eq!(union.len(), 5);
+    }
+
+    #[test]
+    fn fill_rules_are_respected() {
+        let mut nested = square(0.0, 0.0, 4.0);
+
+        nested.update(|builder| {
+            builder.open(Point2::new(1.0, 1.0));
+            builder.line_to(Point2::new(3.0, 1.0));
+            builder.line_to(Point2::new(3.0, 3.0));
+            builder.line_to(Point2::new(1.0, 3.0));
+        });
+
+        let empty = Path::new();
+        let ring = nested.union(&empty, FillRule::EvenOdd, 0.1);
+
+        assert_eq!(ring.area(), 12.0);
+        // the hole is counter-clockwise, so it is also a hole with the non-zero rule
+        assert!(!ring.union(&empty, FillRule::NonZero, 0.1).is_empty());
+        assert_eq!(ring.union(&empty, FillRule::NonZero, 0.1).area(), 12.0);
+        assert_eq!(nested.union(&empty, FillRule::NonZero, 0.1).area(), 16.0);
+    }
+
+    #[test]
+    fn curves_are_flattened_within_tolerance() {
+        let a = Path::from(Circle::new(Point2::new(0.0, 0.0), 10.0));
+        let b = Path::from(Circle::new(Point2::new(10.0, 0.0), 10.0));
+        // the area of the lens between 2 circles of radius `r` with centers `r` apart
+        let lens = (2.0 * std::f32::consts::PI / 3.0 - 3.0f32.sqrt() / 2.0) * 100.0;
+
+        let intersection = a.intersection(&b, FillRule::NonZero, 0.01);
+
+        assert!((intersection.area() - lens).abs() < 0.5, "{}", intersection.area());
+        assert!(
+            (a.union(&b, FillRule::NonZero, 0.01).area() - (a.area() * 2.0 - lens)).abs() < 1.0
+        );
+    }
+}
@@ -1,5 +1,6 @@
+use super::arc::Arc;
 use super::{Path, Verb};
-use crate::math::Point2;
+use crate::math::{Point2, Vec2};
 
 /// An interface for creating and modifying [`Path`]s.
 pub struct PathBuilder {
@@ -12,12 +13,7 @@ pub struct PathBuilder {
 impl PathBuilder {
     /// Returns a new path builder.
     pub const fn new() -> Self {
-        Self {
-            points: Vec::new(),
-            verbs: Vec::new(),
-            move_required: true,
-            last_point: None,
-        }
+        Self { points: Vec::new(), verbs: Vec::new(), move_required: true, last_point: None }
     }
 
     /// Returns the amount of elements in this path.
@@ -75,6 +71,64 @@ impl PathBuilder {
         self.points.extend([p.into(), c1.into(), c2.into()]);
     }
 
+    /// Adds an elliptical arc described by its center to this path.
+    ///
+    /// Angles are in radians, clockwise from the `x`-axis (`y`-down), and
+    /// `x_rotation` rotates the axes of the ellipse. If the path isn't at the
+    /// start of the arc, a line is added to it first. The arc is approximated
+    /// with cubic segments that deviate from it by at most `tolerance`, or as
+    /// little as possible if `tolerance` isn't positive. Arcs with a sweep that
+    /// isn't finite are skipped.
+    pub fn arc_to(
+        &mut self,
+        center: impl Into<Point2<f32>>,
+        radii: Vec2<f32>,
+        start_angle: f32,
+        sweep_angle: f32,
+        x_rotation: f32,
+        tolerance: f32,
+    ) {
+        let arc = Arc { center: center.into(), radii, x_rotation, start_angle, sweep_angle };
+        let start = arc.point(start_angle);
+
+        if self.move_required {
+            self.open(start);
+        } else if self.points.last() != Some(&start) {
+            self.line_to(start);
+        }
+
+        arc.append_to(self, tolerance);
+    }
+
+    /// Adds an elliptical arc from the current point to `to`, like the `A`
+    /// command of SVG path data.
+    ///
+    /// Of the 4 possible arcs, `large_arc` selects one spanning more than 180°,
+    /// and `sweep` selects one going clockwise (`y`-down). Radii that are too
+    /// small to reach `to` are scaled up, and an arc with a zero radius is a
+    /// line. The arc is approximated with cubic segments that deviate from it
+    /// by at most `tolerance`.
+    pub fn svg_arc_to(
+        &mut self,
+        radii: Vec2<f32>,
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: impl Into<Point2<f32>>,
+        tolerance: f32,
+    ) {
+        let to = to.into();
+
+        self.move_if_required();
+
+        let from = *self.points.last().expect("a subpath is open");
+
+        match Arc::from_endpoints(from, to, radii, x_rotation, large_arc, sweep) {
+            Some(arc) => arc.append_to(self, tolerance),
+            None => self.line_to(to),
+        }
+    }
+
     /// Closes the current subpath.
     pub fn close(&mut self) {
         if !self.verbs.is_empty() {
@@ -110,6 +164,19 @@ impl PathBuilder {
         Path { points, verbs }
     }
 
+    /// Builds a new [`Path`] without closing the current subpath.
+    pub(crate) fn build_open(self) -> Path {
+        let Self { mut points, mut verbs, .. } = self;
+
+        // if just a move, delete it
+        if verbs.len() == 1 {
+            points.clear();
+            verbs.clear();
+        }
+
+        Path { points, verbs }
+    }
+
     fn move_if_required(&mut self) {
         if self.move_required {
             if let Some(idx) = self.last_point {
@@ -0,0 +1,150 @@
+use std::mem;
+
+use super::{Path, PathElement, PathElements, PathSegment};
+use crate::math::{Point2, Polyline};
+
+/// Iterator of the subpaths of a [`Path`] flattened into [`Polyline`]s.
+pub struct PathPolylines<'a> {
+    elements: PathElements<'a>,
+    tolerance: f32,
+    points: Vec<Point2<f32>>,
+    last: Point2<f32>,
+}
+
+impl Path {
+    /// Returns an iterator of the subpaths of this path flattened into
+    /// polylines.
+    ///
+    /// Curves are subdivided until the lines deviate from them by at most
+    /// `tolerance`. Consecutive duplicate points are removed, and closed
+    /// polylines don't repeat their first point.
+    pub fn flatten(&self, tolerance: f32) -> PathPolylines<'_> {
+        PathPolylines {
+            elements: self.iter(),
+            tolerance,
+            points: Vec::new(),
+            last: Point2::new(0.0, 0.0),
+        }
+    }
+}
+
+impl PathPolylines<'_> {
+    fn finish(&mut self, closed: bool) -> Option<Polyline<f32>> {
+        let mut points = mem::take(&mut self.points);
+
+        // the closing line is implied
+        if closed && points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        (!points.is_empty()).then(|| Polyline::new(points, closed))
+    }
+}
+
+impl Iterator for PathPolylines<'_> {
+    type Item = Polyline<f32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(element) = self.elements.next() {
+            let last = self.last;
+            let segment = match element {
+                PathElement::Open(p) => {
+                    let polyline = self.finish(false);
+
+                    self.points.push(p);
+                    self.last = p;
+
+                    match polyline {
+                        Some(polyline) => return Some(polyline),
+                        None => continue,
+                    }
+                },
+                PathElement::LineTo(p) => PathSegment::Line([last, p]),
+                PathElement::QuadTo { p, c } => PathSegment::Quad([last, p, c]),
+                PathElement::CubicTo { p, c1, c2 } => PathSegment::Cubic([last, p, c1, c2]),
+                PathElement::Close => {
+                    self.last = self.points.first().copied().unwrap_or(last);
+
+                    match self.finish(true) {
+                        Some(polyline) => return Some(polyline),
+                        None => continue,
+                    }
+                },
+            };
+
+            // segments after a close start where it ended
+            if self.points.is_empty() {
+                self.points.push(last);
+            }
+
+            let points = &mut self.points;
+
+            segment.flatten(self.tolerance, &mut |point| {
+                if points.last() != Some(&point) {
+                    points.push(point);
+                }
+            });
+            self.last = segment.end();
+        }
+
+        self.finish(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{Circle, Rect, Size2};
+
+    #[test]
+    fn lines_are_kept() {
+        let path = Path::from_fn(|builder| {
+            builder.open(Point2::new(5.0, 5.0));
+            builder.line_to(Point2::new(6.0, 5.0));
+            builder.open(Point2::new(0.0, 0.0));
+            builder.line_to(Point2::new(1.0, 0.0));
+            builder.line_to(Point2::new(1.0, 0.0));
+            builder.line_to(Point2::new(1.0, 1.0));
+            builder.close();
+        });
+        let rect = Path::from(Rect::new(Point2::new(0.0, 0.0), Size2::splat(1.0)));
+
+        assert_eq!(
+            path.flatten(0.1).collect::<Vec<_>>(),
+            [
+                Polyline::new([Point2::new(5.0, 5.0), Point2::new(6.0, 5.0)], false),
+                Polyline::new(
+                    [Point2::new(0.0, 0.0), Point2::new(1.0, 0.0), Point2::new(1.0, 1.0)],
+                    true,
+                ),
+            ],
+        );
+        assert_eq!(rect.flatten(0.1).count(), 1);
+        assert!(rect.flatten(0.1).all(|polyline| polyline.closed && polyline.points.len() == 4));
+        assert_eq!(Path::new().flatten(0.1).count(), 0);
+    }
+
+    #[test]
+    fn curves_are_within_tolerance() {
+        let center = Point2::new(2.0, 3.0);
+        let path = Path::from(Circle::new(center, 10.0));
+
+        for tolerance in [0.1, 0.01] {
+            let polylines: Vec<_> = path.flatten(tolerance).collect();
+            let points = &polylines[0].points;
+
+            assert_eq!(polylines.len(), 1);
+            assert!(polylines[0].closed);
+            assert!(points.len() > 8);
+
+            for (i, &point) in points.iter().enumerate() {
+                let next = points[(i + 1) % points.len()];
+                let middle = Point2::new((point.x + next.x) / 2.0, (point.y + next.y) / 2.0);
+                let distance = |p: Point2<f32>| (p.x - center.x).hypot(p.y - center.y);
+
+                assert!((distance(point) - 10.0).abs() <= tolerance);
+                assert!((distance(middle) - 10.0).abs() <= tolerance);
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use super::{Path, PathSegments};
+use crate::element::FillRule;
+use crate::math::{Affine2, Point2, Rect, Size2, Zero};
+
+impl Path {
+    /// Returns an iterator of the [`PathSegment`](super::PathSegment)s of this path.
+    ///
+    /// Closed subpaths end with a line back to their start, unless they already
+    /// end there.
+    pub fn segments(&self) -> PathSegments<'_> {
+        PathSegments {
+            elements: self.iter(),
+            start: Point2::new(0.0, 0.0),
+            last: Point2::new(0.0, 0.0),
+            close_all: false,
+        }
+    }
+
+    /// Returns an iterator of segments where every subpath is closed, as when
+    /// filling.
+    pub(crate) fn filled_segments(&self) -> PathSegments<'_> {
+        PathSegments { close_all: true, ..self.segments() }
+    }
+
+    /// Applies a transform to every point of this path.
+    pub fn transform(&mut self, transform: Affine2<f32>) {
+        for point in &mut self.points {
+            *point = transform.transform_point(*point);
+        }
+    }
+
+    /// Returns this path with a transform applied to it.
+    pub fn transformed(mut self, transform: Affine2<f32>) -> Self {
+        self.transform(transform);
+
+        self
+    }
+
+    /// Returns the smallest rectangle containing this path.
+    ///
+    /// Returns [`Rect::ZERO`] if the path is empty.
+    pub fn bounds(&self) -> Rect<f32> {
+        self.segments()
+            .map(|segment| segment.bounds())
+            .reduce(union)
+            .or_else(|| self.points.first().copied().map(Rect::from_origin))
+            .unwrap_or(Rect::ZERO)
+    }
+
+    /// Returns the smallest rectangle containing every point of this path,
+    /// including control points.
+    ///
+    /// This is cheaper than [`Path::bounds`], but may be larger.
+    pub fn control_bounds(&self) -> Rect<f32> {
+        self.points
+            .iter()
+            .map(|&point| Rect::from_origin(point))
+            .reduce(union)
+            .unwrap_or(Rect::ZERO)
+    }
+
+    /// Returns the signed area of this path, treating every subpath as closed.
+    ///
+    /// Subpaths drawn clockwise (`y`-down) have a positive area.
+    pub fn area(&self) -> f32 {
+        self.filled_segments().map(|segment| segment.signed_area()).sum()
+    }
+
+    /// Returns the total length of the segments of this path, accurate to
+    /// within `accuracy`.
+    pub fn perimeter(&self, accuracy: f32) -> f32 {
+        let segments = self.segments().count().max(1) as f32;
+
+        self.segments().map(|segment| segment.arclen(accuracy / segments)).sum()
+    }
+
+    /// Returns the winding number of a point, treating every subpath as
+    /// closed.
+    ///
+    /// Subpaths drawn clockwise (`y`-down) around the point add `1`, and
+    /// counter-clockwise subpaths subtract `1`.
+    pub fn winding(&self, point: Point2<f32>) -> i32 {
+        self.filled_segments().map(|segment| segment.winding(point)).sum()
+    }
+
+    /// Returns `true` if a point is inside this path when filled with a rule.
+    pub fn contains(&self, point: Point2<f32>, rule: FillRule) -> bool {
+        rule.is_filled(self.winding(point))
+    }
+}
+
+fn union(a: Rect<f32>, b: Rect<f32>) -> Rect<f32> {
+    let min = Point2::new(a.left().min(b.left()), a.top().min(b.top()));
+    let max = Point2::new(a.right().max(b.right()), a.bottom().max(b.bottom()));
+
+    Rect::new(min, Size2::new(max.x - min.x, max.y - min.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+    use super::*;
+    use crate::math::{Circle, Line, Vec2};
+
+    #[test]
+    fn bounds_use_curve_extrema() {
+        let circle = Circle::new(Point2::new(10.0, 20.0), 5.0);
+        let path = Path::from(circle);
+        let bounds = path.bounds();
+
+        assert!((bounds.origin.x - 5.0).abs() < 1e-4 && (bounds.origin.y - 15.0).abs() < 1e-4);
+        assert!((bounds.size.w - 10.0).abs() < 1e-4 && (bounds.size.h - 10.0).abs() < 1e-4);
+        // the control points of the cubics are outside of the circle
+        assert!(path.control_bounds().contains(bounds));
+        assert!(path.control_bounds().size.w > 10.0);
+        assert_eq!(Path::new().bounds(), Rect::ZERO);
+    }
+
+    #[test]
+    fn area_and_perimeter() {
+        let rect = Path::from(Rect::new(Point2::new(1.0, 1.0), Size2::new(2.0, 3.0)));
+
+        assert_eq!(rect.area(), 6.0);
+        assert_eq!(rect.perimeter(0.0), 10.0);
+
+        let circle = Path::from(Circle::new(Point2::new(3.0, 4.0), 10.0));
+
+        assert!((circle.area() - PI * 100.0).abs() < 0.1);
+        assert!((circle.perimeter(1e-3) - TAU * 10.0).abs() < 0.01);
+
+        // open subpaths are closed for the area, but not the perimeter
+        let line = Path::from(Line::new(Point2::new(0.0, 0.0), Point2::new(3.0, 4.0)));
+
+        assert_eq!(line.area(), 0.0);
+        assert_eq!(line.perimeter(0.0), 5.0);
+    }
+
+    #[test]
+    fn winding_counts_clockwise_subpaths() {
+        let rect = Path::from(Rect::new(Point2::new(0.0, 0.0), Size2::splat(10.0)));
+        let circle = Path::from(Circle::new(Point2::new(5.0, 5.0), 3.0));
+
+        assert_eq!(rect.winding(Point2::new(5.0, 5.0)), 1);
+        assert_eq!(rect.winding(Point2::new(15.0, 5.0)), 0);
+        // on the corner of the ray
+        assert_eq!(rect.winding(Point2::new(5.0, 0.0)), 1);
+        assert_eq!(circle.winding(Point2::new(5.0, 5.0)), 1);
+        assert_eq!(circle.winding(Point2::new(5.0, 2.5)), 1);
+        assert_eq!(circle.winding(Point2::new(5.0, 1.5)), 0);
+
+        let mirrored = rect.transformed(Affine2::from_scale(Vec2::new(-1.0, 1.0)));
+
+        assert_eq!(mirrored.winding(Point2::new(-5.0, 5.0)), -1);
+        assert_eq!(mirrored.area(), -100.0);
+    }
+
+    #[test]
+    fn contains_follows_the_fill_rule() {
+        let rings = Path::from_fn(|builder| {
+            for radius in [10.0, 5.0] {
+                builder.open(Point2::new(-radius, -radius));
+                builder.line_to(Point2::new(radius, -radius));
+                builder.line_to(Point2::new(radius, radius));
+                builder.line_to(Point2::new(-radius, radius));
+                builder.close();
+            }
+        });
+        let (center, ring) = (Point2::new(0.0, 0.0), Point2::new(7.0, 0.0));
+
+        assert!(rings.contains(center, FillRule::NonZero));
+        assert!(!rings.contains(center, FillRule::EvenOdd));
+        assert!(rings.contains(ring, FillRule::NonZero));
+        assert!(rings.contains(ring, FillRule::EvenOdd));
+        assert!(!rings.contains(Point2::new(11.0, 0.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn transformed_maps_every_point() {
+        let path = Path::from(Rect::new(Point2::new(0.0, 0.0), Size2::new(2.0, 1.0)))
+            .transformed(Affine2::from_rotation(FRAC_PI_2).then_translate(Vec2::new(1.0, 0.0)));
+        let bounds = path.bounds();
+
+        assert!((bounds.origin.x - 0.0).abs() < 1e-5 && bounds.origin.y.abs() < 1e-5);
+        assert!((bounds.size.w - 1.0).abs() < 1e-5 && (bounds.size.h - 2.0).abs() < 1e-5);
+    }
+}
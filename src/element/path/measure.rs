@@ -0,0 +1,238 @@
+use super::{Path, PathBuilder, PathSegment};
+use crate::math::{Point2, Vec2};
+
+/// Measures distances along a [`Path`].
+///
+/// The lengths of segments are computed once, so that finding points by their
+/// distance along the path is cheap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMeasure {
+    segments: Vec<PathSegment>,
+    /// The distance along the path to the end of each segment.
+    ends: Vec<f32>,
+    accuracy: f32,
+}
+
+impl PathMeasure {
+    /// Measures a path, with lengths accurate to within `accuracy`.
+    pub fn new(path: &Path, accuracy: f32) -> Self {
+        let segments: Vec<_> = path.segments().collect();
+        let segment_accuracy = accuracy / segments.len().max(1) as f32;
+        let ends = segments
+            .iter()
+            .scan(0.0, |length, segment| {
+                *length += segment.arclen(segment_accuracy);
+
+                Some(*length)
+            })
+            .collect();
+
+        Self { segments, ends, accuracy: segment_accuracy }
+    }
+
+    /// Returns the length of the path.
+    pub fn length(&self) -> f32 {
+        self.ends.last().copied().unwrap_or(0.0)
+    }
+
+    /// Returns the point at a distance along the path.
+    ///
+    /// The distance is clamped to the length of the path. Returns `None` if the
+    /// path is empty.
+    pub fn point_at(&self, distance: f32) -> Option<Point2<f32>> {
+        let (segment, t) = self.locate(distance)?;
+
+        Some(segment.eval(t))
+    }
+
+    /// Returns the unit tangent at a distance along the path, pointing in the
+    /// direction of the path.
+    ///
+    /// The distance is clamped to the length of the path. Returns `None` if the
+    /// path is empty.
+    pub fn tangent_at(&self, distance: f32) -> Option<Vec2<f32>> {
+        let (segment, t) = self.locate(distance)?;
+
+        Some(tangent(segment, t))
+    }
+
+    /// Returns the part of the path between 2 distances along it.
+    ///
+    /// The distances are clamped to the length of the path, and the returned
+    /// path is open.
+    pub fn segment(&self, start: f32, end: f32) -> Path {
+        let mut builder = PathBuilder::new();
+        let (start, end) = (start.max(0.0), end.min(self.length()));
+
+        if start >= end {
+            return builder.build_open();
+        }
+
+        let mut last = None;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let segment_start = self.segment_start(i);
+            let segment_end = self.ends[i];
+
+            if segment_end <= start {
+                continue;
+            }
+            if segment_start >= end {
+                break;
+            }
+
+            let t0 =
+                if start > segment_start { self.t_at(segment, start - segment_start) } else { 0.0 };
+            let t1 = if end < segment_end { self.t_at(segment, end - segment_start) } else { 1.0 };
+            let part = if (t0, t1) == (0.0, 1.0) { *segment } else { segment.subsegment(t0, t1) };
+
+            // segments of different subpaths aren't connected
+            if last != Some(part.start()) {
+                builder.open(part.start());
+            }
+
+            match part {
+                PathSegment::Line([_, p1]) => builder.line_to(p1),
+                PathSegment::Quad([_, p1, p2]) => builder.quad_to(p1, p2),
+                PathSegment::Cubic([_, p1, p2, p3]) => builder.cubic_to(p1, p2, p3),
+            }
+
+            last = Some(part.end());
+        }
+
+        builder.build_open()
+    }
+
+    /// Returns the segment at a distance along the path and the value of `t`
+    /// on it.
+    fn locate(&self, distance: f32) -> Option<(&PathSegment, f32)> {
+        let distance = distance.clamp(0.0, self.length());
+        // the first segment that ends after the distance
+        let i = self
+            .ends
+            .partition_point(|&end| end < distance)
+            .min(self.segments.len().checked_sub(1)?);
+        let segment = &self.segments[i];
+
+        Some((segment, self.t_at(segment, distance - self.segment_start(i))))
+    }
+
+    fn segment_start(&self, i: usize) -> f32 {
+        i.checked_sub(1).map_or(0.0, |i| self.ends[i])
+    }
+
+    /// Returns the value of `t` at a distance along a segment.
+    fn t_at(&self, segment: &PathSegment, distance: f32) -> f32 {
+        if let PathSegment::Line([p0, p1]) = segment {
+            let length = (p1.x - p0.x).hypot(p1.y - p0.y);
+
+            return if length > 0.0 { (distance / length).clamp(0.0, 1.0) } else { 0.0 };
+        }
+
+        let (mut low, mut high) = (0.0, 1.0);
+
+        // the arc length is monotonic in `t`
+        for _ in 0..24 {
+            let middle = (low + high) / 2.0;
+
+            if segment.subsegment(0.0, middle).arclen(self.accuracy) < distance {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        (low + high) / 2.0
+    }
+}
+
+/// Returns the unit tangent of a segment at `t`.
+fn tangent(segment: &PathSegment, t: f32) -> Vec2<f32> {
+    // the derivative is zero where control points coincide with the ends, so
+    // move slightly inwards
+    let derivative = [t, t.clamp(1e-3, 1.0 - 1e-3)]
+        .into_iter()
+        .map(|t| segment.derivative(t))
+        .find(|d| d.x != 0.0 || d.y != 0.0)
+        .unwrap_or_else(|| {
+            let (start, end) = (segment.start(), segment.end());
+
+            Vec2::new(end.x - start.x, end.y - start.y)
+        });
+    let length = derivative.x.hypot(derivative.y);
+
+    if length > 0.0 {
+        Vec2::new(derivative.x / length, derivative.y / length)
+    } else {
+        Vec2::new(0.0, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, TAU};
+
+    use super::*;
+    use crate::element::PathElement;
+    use crate::math::{Circle, Rect, Size2};
+
+    fn assert_near(a: Point2<f32>, b: Point2<f32>, tolerance: f32) {
+        assert!((a.x - b.x).abs() < tolerance && (a.y - b.y).abs() < tolerance, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn points_and_tangents_along_a_rect() {
+        let path = Path::from(Rect::new(Point2::new(0.0, 0.0), Size2::new(4.0, 2.0)));
+        let measure = PathMeasure::new(&path, 1e-3);
+
+        assert_eq!(measure.length(), 12.0);
+        assert_eq!(measure.point_at(5.0), Some(Point2::new(4.0, 1.0)));
+        assert_eq!(measure.tangent_at(5.0), Some(Vec2::new(0.0, 1.0)));
+        // the closing segment
+        assert_eq!(measure.point_at(11.0), Some(Point2::new(0.0, 1.0)));
+        assert_eq!(measure.point_at(100.0), Some(Point2::new(0.0, 0.0)));
+        assert_eq!(PathMeasure::new(&Path::new(), 1e-3).point_at(0.0), None);
+    }
+
+    #[test]
+    fn points_along_a_circle() {
+        let center = Point2::new(5.0, 5.0);
+        let measure = PathMeasure::new(&Path::from(Circle::new(center, 10.0)), 1e-3);
+        let quarter = measure.length() / 4.0;
+
+        assert!((measure.length() - TAU * 10.0).abs() < 0.01);
+
+        for i in 0..8 {
+            let angle = i as f32 * FRAC_PI_2 / 2.0;
+            let (sin, cos) = angle.sin_cos();
+            let point = measure.point_at(quarter * i as f32 / 2.0).unwrap();
+            let tangent = measure.tangent_at(quarter * i as f32 / 2.0).unwrap();
+
+            assert_near(point, Point2::new(center.x + 10.0 * cos, center.y + 10.0 * sin), 0.01);
+            assert_near(tangent.to_point(), Point2::new(-sin, cos), 1e-3);
+        }
+    }
+
+    #[test]
+    fn segments_are_open_and_follow_the_path() {
+        let path = Path::from(Rect::new(Point2::new(0.0, 0.0), Size2::splat(2.0)));
+        let measure = PathMeasure::new(&path, 1e-3);
+
+        assert_eq!(
+            measure.segment(1.0, 5.0).iter().collect::<Vec<_>>(),
+            [
+                PathElement::Open(Point2::new(1.0, 0.0)),
+                PathElement::LineTo(Point2::new(2.0, 0.0)),
+                PathElement::LineTo(Point2::new(2.0, 2.0)),
+                PathElement::LineTo(Point2::new(1.0, 2.0)),
+            ],
+        );
+        assert!(measure.segment(3.0, 1.0).is_empty());
+
+        let circle = PathMeasure::new(&Path::from(Circle::new(Point2::new(0.0, 0.0), 1.0)), 1e-4);
+        let half = PathMeasure::new(&circle.segment(0.0, circle.length() / 2.0), 1e-4);
+
+        assert!((half.length() - circle.length() / 2.0).abs() < 1e-3);
+        assert_near(half.point_at(half.length()).unwrap(), Point2::new(-1.0, 0.0), 1e-3);
+    }
+}
@@ -1,13 +1,26 @@
 use std::{fmt, mem};
 
+pub(crate) use self::arc::QUARTER_TURNS;
 pub use self::builder::*;
+pub use self::flatten::*;
 pub use self::iter::*;
+pub use self::measure::*;
+pub use self::segment::*;
+pub use self::svg::*;
 use crate::math::Point2;
 
+mod arc;
+mod boolean;
 mod builder;
+mod flatten;
+mod geometry;
 mod iter;
+mod measure;
+mod outline;
+mod segment;
 #[cfg(feature = "renderer")]
 mod shape;
+mod svg;
 
 // TODO: make this wrap a `kurbo::BezPath`
 
@@ -118,7 +131,35 @@ impl<'a> IntoIterator for &'a Path {
 
 #[cfg(test)]
 mod tests {
+    use std::f32::consts::{FRAC_PI_2, TAU};
+
     use super::*;
+    use crate::math::Vec2;
+
+    /// Returns the distance of the midpoint of each cubic segment from a point.
+    fn cubic_midpoint_distances(path: &Path, center: Point2<f32>) -> Vec<f32> {
+        let mut last = Point2::new(0.0, 0.0);
+
+        path.iter()
+            .filter_map(|element| {
+                let (from, [c1, c2, to]) = match element {
+                    PathElement::Open(p) | PathElement::LineTo(p) => {
+                        last = p;
+
+                        return None;
+                    },
+                    PathElement::CubicTo { p, c1, c2 } => {
+                        (mem::replace(&mut last, c2), [p, c1, c2])
+                    },
+                    _ => return None,
+                };
+                let x = (from.x + 3.0 * c1.x + 3.0 * c2.x + to.x) / 8.0;
+                let y = (from.y + 3.0 * c1.y + 3.0 * c2.y + to.y) / 8.0;
+
+                Some((x - center.x).hypot(y - center.y))
+            })
+            .collect()
+    }
 
     #[test]
     fn new_path_is_empty() {
@@ -175,4 +216,92 @@ mod tests {
 
         assert_eq!(path, original);
     }
+
+    #[test]
+    fn arcs_are_within_tolerance() {
+        let center = Point2::new(5.0, 5.0);
+        let coarse = Path::from_fn(|builder| {
+            builder.arc_to(center, Vec2::new(100.0, 100.0), 0.0, TAU, 0.0, 1.0);
+        });
+        let fine = Path::from_fn(|builder| {
+            builder.arc_to(center, Vec2::new(100.0, 100.0), 0.0, TAU, 0.0, 0.001);
+        });
+
+        let coarse = cubic_midpoint_distances(&coarse, center);
+        let fine = cubic_midpoint_distances(&fine, center);
+
+        assert_eq!(coarse.len(), 4);
+        assert!(fine.len() > coarse.len());
+        assert!(coarse.iter().all(|distance| (distance - 100.0).abs() < 1.0));
+        assert!(fine.iter().all(|distance| (distance - 100.0).abs() < 0.001 * 10.0));
+    }
+
+    #[test]
+    fn arcs_without_tolerance_have_bounded_segments() {
+        let center = Point2::new(5.0, 5.0);
+
+        for tolerance in [0.0, -1.0, f32::NAN] {
+            let path = Path::from_fn(|builder| {
+                builder.arc_to(center, Vec2::new(100.0, 100.0), 0.0, TAU, 0.0, tolerance);
+            });
+            let distances = cubic_midpoint_distances(&path, center);
+
+            assert_eq!(distances.len(), 1024);
+            assert!(distances.iter().all(|distance| (distance - 100.0).abs() < 1e-3));
+        }
+
+        let path = Path::from_fn(|builder| {
+            builder.arc_to(center, Vec2::new(100.0, 100.0), 0.0, f32::NAN, 0.0, 0.1);
+        });
+
+        assert!(cubic_midpoint_distances(&path, center).is_empty());
+    }
+
+    #[test]
+    fn arc_to_connects_to_the_current_point() {
+        let path = Path::from_fn(|builder| {
+            builder.open(Point2::new(0.0, 0.0));
+            builder.arc_to(Point2::new(0.0, 0.0), Vec2::new(1.0, 2.0), 0.0, FRAC_PI_2, 0.0, 0.1);
+        });
+        let mut elements = path.iter();
+
+        assert_eq!(elements.nth(1), Some(PathElement::LineTo(Point2::new(1.0, 0.0))));
+        assert!(matches!(
+            elements.next(),
+            Some(PathElement::CubicTo { c2, .. }) if c2.x.abs() < 1e-6 && (c2.y - 2.0).abs() < 1e-6,
+        ));
+    }
+
+    #[test]
+    fn svg_arcs_select_the_arc_with_flags() {
+        let arc = |large_arc, sweep| {
+            let path = Path::from_fn(|builder| {
+                builder.open(Point2::new(0.0, 0.0));
+                builder.svg_arc_to(
+                    Vec2::new(10.0, 10.0),
+                    0.0,
+                    large_arc,
+                    sweep,
+                    Point2::new(10.0, 0.0),
+                    0.1,
+                );
+            });
+
+            cubic_midpoint_distances(&path, Point2::new(5.0, 0.0)).len()
+        };
+
+        // radii larger than needed give two different circles
+        assert!(arc(true, true) > arc(false, true));
+        assert_eq!(arc(true, false), arc(true, true));
+
+        let half = Path::from_fn(|builder| {
+            builder.open(Point2::new(-10.0, 0.0));
+            builder.svg_arc_to(Vec2::new(1.0, 1.0), 0.0, false, true, Point2::new(10.0, 0.0), 0.01);
+        });
+
+        // the radius is scaled up to reach the endpoint
+        let distances = cubic_midpoint_distances(&half, Point2::new(0.0, 0.0));
+
+        assert!(distances.iter().all(|distance| (distance - 10.0).abs() < 0.01));
+    }
 }
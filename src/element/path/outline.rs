@@ -0,0 +1,435 @@
+use std::f32::consts::{PI, TAU};
+
+use super::{Path, PathBuilder};
+use crate::element::{Cap, FillRule, Join, StrokeStyle};
+use crate::math::{Point2, Polyline, Vec2};
+
+impl Path {
+    /// Returns the outline of a stroke of this path, which can be filled to
+    /// draw the stroke.
+    ///
+    /// Curves, round joins and round caps are flattened into lines that deviate
+    /// from them by at most `tolerance`. The outline has no overlapping
+    /// subpaths, so it can be filled with either rule.
+    pub fn stroke_outline(&self, style: &StrokeStyle, tolerance: f32) -> Path {
+        self.stroke_subpaths(style, tolerance).union(&Path::new(), FillRule::NonZero, tolerance)
+    }
+
+    /// Returns `true` if a point is within a stroke of this path.
+    ///
+    /// Curves, round joins and round caps are flattened into lines that deviate
+    /// from them by at most `tolerance`.
+    pub fn hit_stroke(&self, point: Point2<f32>, style: &StrokeStyle, tolerance: f32) -> bool {
+        let bounds = self.control_bounds().with_insets(Vec2::splat(-style.reach()));
+
+        bounds.contains_point(point) && self.stroke_subpaths(style, tolerance).winding(point) != 0
+    }
+
+    /// Returns subpaths that cover a stroke of this path when filled with
+    /// [`FillRule::NonZero`], which may overlap.
+    fn stroke_subpaths(&self, style: &StrokeStyle, tolerance: f32) -> Path {
+        let mut outline = Outline {
+            builder: PathBuilder::new(),
+            half_width: style.width.abs() / 2.0,
+            style,
+            tolerance,
+        };
+
+        if outline.half_width == 0.0 {
+            return Path::new();
+        }
+
+        for Polyline { points, closed } in self.flatten(tolerance) {
+            match style.dashes() {
+                Some(dashes) => {
+                    for dash in dash(&points, closed, dashes, style.dash_offset) {
+                        outline.polyline(&dash, false);
+                    }
+                },
+                None => outline.polyline(&points, closed),
+            }
+        }
+
+        outline.builder.build()
+    }
+}
+
+/// Builds subpaths that cover a stroke when filled with
+/// [`FillRule::NonZero`].
+///
+/// Each polyline is outlined by its offset on either side, connected by joins
+/// and caps. Inner joins cut the offsets short where they intersect, or go
+/// through the vertex when the lines are too short, so that the outline winds
+/// at least once around every point of the stroke.
+struct Outline<'a> {
+    builder: PathBuilder,
+    half_width: f32,
+    style: &'a StrokeStyle,
+    tolerance: f32,
+}
+
+impl Outline<'_> {
+    fn polyline(&mut self, points: &[Point2<f32>], closed: bool) {
+        let mut points = points.to_vec();
+
+        points.dedup();
+
+        if closed && points.len() > 1 {
+            let mut reversed = points.clone();
+
+            reversed.reverse();
+
+            for points in [points, reversed] {
+                let mut side = Vec::new();
+
+                for i in 0..points.len() {
+                    let previous = points[(i + points.len() - 1) % points.len()];
+
+                    self.join(previous, points[i], points[(i + 1) % points.len()], &mut side);
+                }
+
+                self.subpath(&side);
+            }
+
+            return;
+        }
+
+        if points.len() < 2 {
+            // a zero-length subpath only has caps
+            if let Some(&point) = points.first() {
+                self.dot(point);
+            }
+
+            return;
+        }
+
+        let reversed: Vec<_> = points.iter().rev().copied().collect();
+        let mut outline = Vec::new();
+
+        // along the right side, then back along the left side
+        for (points, cap) in [(&points, self.style.end), (&reversed, self.style.start)] {
+            let len = points.len();
+
+            outline.push(points[0] + self.normal(points[0], points[1]));
+
+            for i in 1..len - 1 {
+                self.join(points[i - 1], points[i], points[i + 1], &mut outline);
+            }
+
+            self.cap(points[len - 2], points[len - 1], cap, &mut outline);
+        }
+
+        self.subpath(&outline);
+    }
+
+    /// Adds the points of the right side (`y`-down) of a join at `point`
+    /// between the lines from `previous` and to `next`.
+    fn join(
+        &self,
+        previous: Point2<f32>,
+        point: Point2<f32>,
+        next: Point2<f32>,
+        out: &mut Vec<Point2<f32>>,
+    ) {
+        let (n0, n1) = (self.normal(previous, point), self.normal(point, next));
+        let cross = n0.x * n1.y - n0.y * n1.x;
+        let dot = n0.x * n1.x + n0.y * n1.y;
+
+        if cross == 0.0 && dot > 0.0 {
+            out.push(point + n0);
+
+            return;
+        }
+
+        let h2 = self.half_width * self.half_width;
+        // where the offsets of the lines intersect
+        let miter = (h2 + dot > 0.0).then(|| {
+            let scale = h2 / (h2 + dot);
+
+            Vec2::new((n0.x + n1.x) * scale, (n0.y + n1.y) * scale)
+        });
+
+        // turning right, so this is the inner side
+        if cross > 0.0 {
+            // the offsets can only be cut short where they intersect if they're long enough
+            let fits = miter.is_some_and(|miter| {
+                let along = |from: Point2<f32>, to: Point2<f32>| {
+                    let (dx, dy) = (to.x - from.x, to.y - from.y);
+
+                    (miter.x * dx + miter.y * dy).abs() <= dx * dx + dy * dy
+                };
+
+                along(previous, point) && along(point, next)
+            });
+
+            match miter {
+                Some(miter) if fits => out.push(point + miter),
+                _ => out.extend([point + n0, point, point + n1]),
+            }
+
+            return;
+        }
+
+        match self.style.join {
+            Join::Bevel => out.extend([point + n0, point + n1]),
+            Join::Miter => {
+                // `1 / cos(φ / 2)`, where `φ` is the angle between the normals
+                let ratio = (2.0 * h2 / (h2 + dot)).sqrt();
+
+                match miter {
+                    Some(miter) if ratio <= self.style.miter_limit => {
+                        out.extend([point + n0, point + miter, point + n1]);
+                    },
+                    _ => out.extend([point + n0, point + n1]),
+                }
+            },
+            Join::Round => {
+                let start = n0.y.atan2(n0.x);
+                let mut sweep = n1.y.atan2(n1.x) - start;
+
+                // the outer side is the shorter way around, or around the front when
+                // turning back
+                if cross == 0.0 {
+                    sweep = -PI;
+                } else if sweep > PI {
+                    sweep -= TAU;
+                } else if sweep < -PI {
+                    sweep += TAU;
+                }
+
+                out.extend(self.arc(point, start, sweep));
+            },
+        }
+    }
+
+    /// Adds the points of a cap at `end` of the line from `from`, going from
+    /// its right side to its left side.
+    fn cap(&self, from: Point2<f32>, end: Point2<f32>, cap: Cap, out: &mut Vec<Point2<f32>>) {
+        let normal = self.normal(from, end);
+        let direction = Vec2::new(normal.y, -normal.x);
+
+        match cap {
+            Cap::Butt => out.extend([end + normal, end - normal]),
+            Cap::Square => out.extend([
+                end + normal,
+                end + normal + direction,
+                end - normal + direction,
+                end - normal,
+            ]),
+            Cap::Round => out.extend(self.arc(end, normal.y.atan2(normal.x), -PI)),
+        }
+    }
+
+    /// Adds the caps of a zero-length subpath.
+    fn dot(&mut self, point: Point2<f32>) {
+        let h = self.half_width;
+
+        match self.style.start {
+            Cap::Butt => {},
+            Cap::Square => self.subpath(&[
+                Point2::new(point.x - h, point.y - h),
+                Point2::new(point.x + h, point.y - h),
+                Point2::new(point.x + h, point.y + h),
+                Point2::new(point.x - h, point.y + h),
+            ]),
+            Cap::Round => {
+                let points: Vec<_> = self.arc(point, 0.0, TAU).collect();
+
+                self.subpath(&points);
+            },
+        }
+    }
+
+    /// Returns points along an arc with the stroke's radius, including its
+    /// ends.
+    fn arc(
+        &self,
+        center: Point2<f32>,
+        start: f32,
+        sweep: f32,
+    ) -> impl Iterator<Item = Point2<f32>> {
+        let radius = self.half_width;
+        // the largest angle with a chord within `tolerance` of the arc
+        let step = 2.0 * (1.0 - self.tolerance / radius).clamp(-1.0, 1.0).acos();
+        let steps = (sweep.abs() / step.max(1e-3)).ceil().clamp(1.0, 1024.0) as usize;
+
+        (0..=steps).map(move |i| {
+            let (sin, cos) = (start + sweep * i as f32 / steps as f32).sin_cos();
+
+            Point2::new(center.x + radius * cos, center.y + radius * sin)
+        })
+    }
+
+    /// Returns the normal of a line with the length of half of the stroke
+    /// width, pointing to its right (`y`-down).
+    fn normal(&self, from: Point2<f32>, to: Point2<f32>) -> Vec2<f32> {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let scale = self.half_width / dx.hypot(dy);
+
+        Vec2::new(-dy * scale, dx * scale)
+    }
+
+    fn subpath(&mut self, points: &[Point2<f32>]) {
+        let Some((&first, rest)) = points.split_first() else {
+            return;
+        };
+
+        self.builder.open(first);
+
+        for &point in rest {
+            self.builder.line_to(point);
+        }
+
+        self.builder.close();
+    }
+}
+
+/// Splits a polyline into dashes.
+fn dash(
+    points: &[Point2<f32>],
+    closed: bool,
+    dashes: impl Iterator<Item = f32> + Clone,
+    offset: f32,
+) -> Vec<Vec<Point2<f32>>> {
+    let pattern: Vec<_> = dashes.map(|length| length.max(0.0)).collect();
+    let period: f32 = pattern.iter().sum();
+
+    // start partway into the pattern
+    let mut index = 0;
+    let mut remaining = pattern[0];
+    let mut offset = offset.rem_euclid(period);
+
+    while offset > 0.0 {
+        if offset < remaining {
+            remaining -= offset;
+            offset = 0.0;
+        } else {
+            offset -= remaining;
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+    }
+
+    let mut dashes = Vec::new();
+    let mut current = vec![points[0]];
+    let lines = points.windows(2).map(|pair| (pair[0], pair[1]));
+    let closing = closed.then(|| (points[points.len() - 1], points[0]));
+
+    for (from, to) in lines.chain(closing) {
+        let length = (to.x - from.x).hypot(to.y - from.y);
+        let mut position = 0.0;
+
+        while length - position > remaining {
+            position += remaining;
+
+            let t = position / length;
+            let point = Point2::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+
+            // dashes are at even indices
+            if index % 2 == 0 {
+                current.push(point);
+                dashes.push(std::mem::take(&mut current));
+            } else {
+                current = vec![point];
+            }
+
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+
+        remaining -= length - position;
+
+        if index % 2 == 0 {
+            current.push(to);
+        }
+    }
+
+    if index % 2 == 0 {
+        dashes.push(current);
+    }
+
+    dashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::PathElement;
+    use crate::math::{Line, Rect, Size2};
+
+    fn line() -> Path {
+        Path::from(Line::new(Point2::new(0.0, 0.0), Point2::new(10.0, 0.0)))
+    }
+
+    fn assert_area(path: &Path, area: f32) {
+        assert!((path.area() - area).abs() < 0.02, "{} != {area}", path.area());
+    }
+
+    #[test]
+    fn caps() {
+        let style = |cap| StrokeStyle::new(2.0).with_cap(cap);
+
+        assert_area(&line().stroke_outline(&style(Cap::Butt), 1e-3), 20.0);
+        assert_area(&line().stroke_outline(&style(Cap::Square), 1e-3), 24.0);
+        assert_area(&line().stroke_outline(&style(Cap::Round), 1e-3), 20.0 + PI);
+    }
+
+    #[test]
+    fn joins() {
+        let square = Path::from(Rect::new(Point2::new(0.0, 0.0), Size2::splat(10.0)));
+        let style = |join| StrokeStyle::new(2.0).with_join(join);
+        let miter = square.stroke_outline(&style(Join::Miter), 1e-3);
+
+        // a ring between squares of 12 and 8
+        assert_area(&miter, 80.0);
+        assert_eq!(miter.len(), 10);
+        assert_area(&square.stroke_outline(&style(Join::Bevel), 1e-3), 78.0);
+        assert_area(&square.stroke_outline(&style(Join::Round), 1e-3), 80.0 - (4.0 - PI));
+        // the miter ratio of a right angle is `sqrt(2)`
+        assert_area(&square.stroke_outline(&style(Join::Miter).with_miter_limit(1.4), 1e-3), 78.0);
+    }
+
+    #[test]
+    fn dashes() {
+        let style = StrokeStyle::new(2.0).with_cap(Cap::Butt).with_dash_array([2.0, 2.0]);
+        let dashed = line().stroke_outline(&style, 1e-3);
+
+        assert_area(&dashed, 12.0);
+        assert_eq!(dashed.iter().filter(|element| *element == PathElement::Close).count(), 3);
+        assert_area(&line().stroke_outline(&style.with_dash_offset(1.0), 1e-3), 10.0);
+    }
+
+    #[test]
+    fn zero_length_subpaths_have_caps() {
+        let dot = Path::from_fn(|builder| {
+            builder.open(Point2::new(0.0, 0.0));
+            builder.line_to(Point2::new(0.0, 0.0));
+        });
+        let style = |cap| StrokeStyle::new(2.0).with_cap(cap);
+
+        assert_area(&dot.stroke_outline(&style(Cap::Round), 1e-3), PI);
+        assert!(dot.stroke_outline(&style(Cap::Butt), 1e-3).is_empty());
+    }
+
+    #[test]
+    fn hit_stroke() {
+        let style = || StrokeStyle::new(2.0).with_cap(Cap::Butt);
+        let hit = |path: &Path, x, y, style: StrokeStyle| {
+            path.hit_stroke(Point2::new(x, y), &style, 1e-3)
+        };
+
+        assert!(hit(&line(), 5.0, 0.5, style()));
+        assert!(hit(&line(), 0.5, -0.9, style()));
+        assert!(!hit(&line(), 5.0, 1.5, style()));
+        assert!(!hit(&line(), -0.5, 0.0, style()));
+        assert!(hit(&line(), -0.5, 0.0, style().with_cap(Cap::Round)));
+        // between dashes
+        assert!(!hit(&line(), 3.0, 0.0, style().with_dash_array([2.0, 2.0])));
+
+        let square = Path::from(Rect::new(Point2::new(0.0, 0.0), Size2::splat(10.0)));
+
+        assert!(hit(&square, 10.9, 10.9, style().with_join(Join::Miter)));
+        assert!(!hit(&square, 10.9, 10.9, style().with_join(Join::Round)));
+        assert!(!hit(&square, 5.0, 5.0, style()));
+    }
+}
@@ -0,0 +1,428 @@
+use super::{PathElement, PathElements};
+use crate::math::{Point2, Rect, Size2, Vec2};
+
+/// A segment of a [`Path`](super::Path), with its points in order.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum PathSegment {
+    Line([Point2<f32>; 2]),
+    Quad([Point2<f32>; 3]),
+    Cubic([Point2<f32>; 4]),
+}
+
+/// Iterator of [`PathSegment`]s of a [`Path`](super::Path).
+pub struct PathSegments<'a> {
+    pub(super) elements: PathElements<'a>,
+    /// The start of the current subpath.
+    pub(super) start: Point2<f32>,
+    pub(super) last: Point2<f32>,
+    /// Whether to add a line closing subpaths that aren't explicitly closed,
+    /// as when filling.
+    pub(super) close_all: bool,
+}
+
+impl PathSegment {
+    /// Returns the first point of this segment.
+    pub fn start(&self) -> Point2<f32> {
+        match self {
+            Self::Line([p0, ..]) | Self::Quad([p0, ..]) | Self::Cubic([p0, ..]) => *p0,
+        }
+    }
+
+    /// Returns the last point of this segment.
+    pub fn end(&self) -> Point2<f32> {
+        match self {
+            Self::Line([.., p1]) => *p1,
+            Self::Quad([.., p2]) => *p2,
+            Self::Cubic([.., p3]) => *p3,
+        }
+    }
+
+    /// Returns the point at `t` in `0..=1`.
+    pub fn eval(&self, t: f32) -> Point2<f32> {
+        match *self {
+            Self::Line([p0, p1]) => lerp(p0, p1, t),
+            Self::Quad([p0, p1, p2]) => lerp(lerp(p0, p1, t), lerp(p1, p2, t), t),
+            Self::Cubic([p0, p1, p2, p3]) => {
+                let (p01, p12, p23) = (lerp(p0, p1, t), lerp(p1, p2, t), lerp(p2, p3, t));
+
+                lerp(lerp(p01, p12, t), lerp(p12, p23, t), t)
+            },
+        }
+    }
+
+    /// Returns the derivative at `t` in `0..=1`.
+    pub fn derivative(&self, t: f32) -> Vec2<f32> {
+        match *self {
+            Self::Line([p0, p1]) => sub(p1, p0),
+            Self::Quad([p0, p1, p2]) => scale(sub(lerp(p1, p2, t), lerp(p0, p1, t)), 2.0),
+            Self::Cubic([p0, p1, p2, p3]) => {
+                let (p01, p12, p23) = (lerp(p0, p1, t), lerp(p1, p2, t), lerp(p2, p3, t));
+
+                scale(sub(lerp(p12, p23, t), lerp(p01, p12, t)), 3.0)
+            },
+        }
+    }
+
+    /// Returns the part of this segment between `t0` and `t1`.
+    pub fn subsegment(&self, t0: f32, t1: f32) -> Self {
+        let (from, to) = (self.eval(t0), self.eval(t1));
+        // the derivative of the subsegment is scaled by its length in `t`
+        let dt = t1 - t0;
+
+        match *self {
+            Self::Line(_) => Self::Line([from, to]),
+            Self::Quad(_) => {
+                let d = scale(self.derivative(t0), dt / 2.0);
+
+                Self::Quad([from, from + d, to])
+            },
+            Self::Cubic(_) => {
+                let (d0, d1) = (self.derivative(t0), self.derivative(t1));
+
+                Self::Cubic([from, from + scale(d0, dt / 3.0), to - scale(d1, dt / 3.0), to])
+            },
+        }
+    }
+
+    /// Returns the values of `t` in `0..1` where the `x` or `y` derivative of
+    /// this segment is zero.
+    pub fn extrema(&self) -> impl Iterator<Item = f32> {
+        let mut extrema = [None; 4];
+
+        match *self {
+            Self::Line(_) => {},
+            Self::Quad([p0, p1, p2]) => {
+                for (i, [a, b, c]) in
+                    [[p0.x, p1.x, p2.x], [p0.y, p1.y, p2.y]].into_iter().enumerate()
+                {
+                    let denominator = a - 2.0 * b + c;
+
+                    if denominator != 0.0 {
+                        extrema[i] = Some((a - b) / denominator);
+                    }
+                }
+            },
+            Self::Cubic([p0, p1, p2, p3]) => {
+                let axes = [[p0.x, p1.x, p2.x, p3.x], [p0.y, p1.y, p2.y, p3.y]];
+
+                for (i, [a, b, c, d]) in axes.into_iter().enumerate() {
+                    // the derivative is a quadratic with these coefficients (divided by 3)
+                    let qa = -a + 3.0 * b - 3.0 * c + d;
+                    let qb = 2.0 * (a - 2.0 * b + c);
+                    let qc = b - a;
+
+                    let [r0, r1] = solve_quadratic(qa, qb, qc);
+
+                    extrema[2 * i] = r0;
+                    extrema[2 * i + 1] = r1;
+                }
+            },
+        }
+
+        extrema.into_iter().flatten().filter(|t| *t > 0.0 && *t < 1.0)
+    }
+
+    /// Returns the smallest rectangle containing this segment.
+    pub fn bounds(&self) -> Rect<f32> {
+        let (mut min, mut max) = (self.start(), self.start());
+
+        for point in self.extrema().map(|t| self.eval(t)).chain([self.end()]) {
+            min = Point2::new(min.x.min(point.x), min.y.min(point.y));
+            max = Point2::new(max.x.max(point.x), max.y.max(point.y));
+        }
+
+        Rect::new(min, Size2::new(max.x - min.x, max.y - min.y))
+    }
+
+    /// Returns the length of this segment, accurate to within `accuracy`.
+    pub fn arclen(&self, accuracy: f32) -> f32 {
+        match *self {
+            Self::Line([p0, p1]) => distance(p0, p1),
+            Self::Quad(_) => self.to_cubic().arclen(accuracy),
+            Self::Cubic(points) => cubic_arclen(points, accuracy, 0),
+        }
+    }
+
+    /// Returns the signed area between this segment and the origin.
+    ///
+    /// Summed over a closed path, this is the area of the path.
+    pub fn signed_area(&self) -> f32 {
+        match *self {
+            Self::Line([p0, p1]) => (p0.x * p1.y - p1.x * p0.y) / 2.0,
+            Self::Quad([p0, p1, p2]) => {
+                (p0.x * (2.0 * p1.y + p2.y) + 2.0 * p1.x * (p2.y - p0.y)
+                    - p2.x * (p0.y + 2.0 * p1.y))
+                    / 6.0
+            },
+            Self::Cubic([p0, p1, p2, p3]) => {
+                (p0.x * (6.0 * p1.y + 3.0 * p2.y + p3.y)
+                    + 3.0
+                        * (p1.x * (-2.0 * p0.y + p2.y + p3.y) - p2.x * (p0.y + p1.y - 2.0 * p3.y))
+                    - p3.x * (p0.y + 3.0 * p1.y + 6.0 * p2.y))
+                    / 20.0
+            },
+        }
+    }
+
+    /// Returns the winding number of a ray from `point` towards `+x` crossing
+    /// this segment.
+    ///
+    /// Crossings going down (`y`-down) count as `1`, and going up as `-1`.
+    pub fn winding(&self, point: Point2<f32>) -> i32 {
+        let mut splits = [0.0; 6];
+        let mut len = 1;
+
+        for t in self.extrema() {
+            splits[len] = t;
+            len += 1;
+        }
+
+        splits[1..len].sort_by(f32::total_cmp);
+        splits[len] = 1.0;
+
+        // sum over the parts where `y` is monotonic
+        splits[..=len]
+            .windows(2)
+            .map(|range| self.monotonic_winding(range[0], range[1], point))
+            .sum()
+    }
+
+    /// Calls `f` with points along this segment, excluding its start, so that
+    /// lines between them deviate from it by at most `tolerance`.
+    ///
+    /// Curves are subdivided until their control points are within `tolerance`
+    /// of their chord.
+    pub(crate) fn flatten(&self, tolerance: f32, f: &mut impl FnMut(Point2<f32>)) {
+        match self.to_cubic() {
+            Self::Cubic(points) if !matches!(self, Self::Line(_)) => {
+                flatten_cubic(points, tolerance, 0, f)
+            },
+            _ => f(self.end()),
+        }
+    }
+
+    /// Returns this segment as a cubic.
+    pub fn to_cubic(&self) -> Self {
+        match *self {
+            Self::Line([p0, p1]) => {
+                Self::Cubic([p0, lerp(p0, p1, 1.0 / 3.0), lerp(p0, p1, 2.0 / 3.0), p1])
+            },
+            Self::Quad([p0, p1, p2]) => {
+                Self::Cubic([p0, lerp(p0, p1, 2.0 / 3.0), lerp(p2, p1, 2.0 / 3.0), p2])
+            },
+            Self::Cubic(_) => *self,
+        }
+    }
+
+    /// Returns the winding number of the part of this segment between `t0` and
+    /// `t1`, where `y` is monotonic.
+    fn monotonic_winding(&self, t0: f32, t1: f32, point: Point2<f32>) -> i32 {
+        let (from, to) = (self.eval(t0), self.eval(t1));
+
+        // half-open, so that a crossing at a shared endpoint is only counted once
+        let (direction, (mut low, mut high)) = if from.y < to.y {
+            if !(from.y <= point.y && point.y < to.y) {
+                return 0;
+            }
+
+            (1, (t0, t1))
+        } else if to.y < from.y {
+            if !(to.y <= point.y && point.y < from.y) {
+                return 0;
+            }
+
+            (-1, (t1, t0))
+        } else {
+            return 0;
+        };
+
+        // fast paths for when the part is entirely to one side
+        let bounds = self.subsegment(t0, t1).bounds();
+
+        if bounds.right() <= point.x {
+            return 0;
+        }
+        if bounds.left() > point.x {
+            return direction;
+        }
+
+        // `low` is where `y` is smallest
+        for _ in 0..32 {
+            let middle = (low + high) / 2.0;
+
+            if self.eval(middle).y < point.y {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        if self.eval((low + high) / 2.0).x > point.x {
+            direction
+        } else {
+            0
+        }
+    }
+}
+
+impl Iterator for PathSegments<'_> {
+    type Item = PathSegment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(element) = self.elements.next() else {
+                if !self.close_all {
+                    return None;
+                }
+
+                // close the last subpath
+                let closing = self.close();
+
+                if closing.is_some() {
+                    self.last = self.start;
+                }
+
+                return closing;
+            };
+            let segment = match element {
+                PathElement::Open(p) => {
+                    let closing = if self.close_all { self.close() } else { None };
+
+                    self.start = p;
+                    self.last = p;
+
+                    match closing {
+                        Some(segment) => return Some(segment),
+                        None => continue,
+                    }
+                },
+                PathElement::LineTo(p) => PathSegment::Line([self.last, p]),
+                PathElement::QuadTo { p, c } => PathSegment::Quad([self.last, p, c]),
+                PathElement::CubicTo { p, c1, c2 } => PathSegment::Cubic([self.last, p, c1, c2]),
+                PathElement::Close => {
+                    let closing = self.close();
+
+                    self.last = self.start;
+
+                    match closing {
+                        Some(segment) => return Some(segment),
+                        None => continue,
+                    }
+                },
+            };
+
+            self.last = segment.end();
+
+            return Some(segment);
+        }
+    }
+}
+
+impl PathSegments<'_> {
+    /// Returns a line from the current point to the start of the subpath, if
+    /// they differ.
+    fn close(&self) -> Option<PathSegment> {
+        (self.last != self.start).then_some(PathSegment::Line([self.last, self.start]))
+    }
+}
+
+/// Returns the length of a cubic by subdividing until its control polygon is
+/// close to its chord.
+fn cubic_arclen(points: [Point2<f32>; 4], accuracy: f32, depth: u32) -> f32 {
+    let [p0, p1, p2, p3] = points;
+    let chord = distance(p0, p3);
+    let polygon = distance(p0, p1) + distance(p1, p2) + distance(p2, p3);
+
+    // from Gravesen, "Adaptive subdivision and the length and energy of Bézier
+    // curves"
+    if polygon - chord <= accuracy || depth >= 16 {
+        return (chord + polygon) / 2.0;
+    }
+
+    let segment = PathSegment::Cubic(points);
+    let (PathSegment::Cubic(mut left), PathSegment::Cubic(mut right)) =
+        (segment.subsegment(0.0, 0.5), segment.subsegment(0.5, 1.0))
+    else {
+        unreachable!("subsegments of cubics are cubics");
+    };
+
+    // evaluating loses precision, so keep the ends exact
+    (left[0], right[3]) = (p0, p3);
+    right[0] = left[3];
+
+    cubic_arclen(left, accuracy / 2.0, depth + 1) + cubic_arclen(right, accuracy / 2.0, depth + 1)
+}
+
+fn flatten_cubic(
+    points: [Point2<f32>; 4],
+    tolerance: f32,
+    depth: u32,
+    f: &mut impl FnMut(Point2<f32>),
+) {
+    let [p0, p1, p2, p3] = points;
+
+    if depth >= 16 || distance_to_line(p1, p0, p3).max(distance_to_line(p2, p0, p3)) <= tolerance {
+        f(p3);
+
+        return;
+    }
+
+    let segment = PathSegment::Cubic(points);
+    let (PathSegment::Cubic(mut left), PathSegment::Cubic(mut right)) =
+        (segment.subsegment(0.0, 0.5), segment.subsegment(0.5, 1.0))
+    else {
+        unreachable!("subsegments of cubics are cubics");
+    };
+
+    // evaluating loses precision, so keep the ends exact to connect to the next
+    // segment
+    (left[0], right[3]) = (p0, p3);
+    right[0] = left[3];
+
+    flatten_cubic(left, tolerance, depth + 1, f);
+    flatten_cubic(right, tolerance, depth + 1, f);
+}
+
+/// Returns the distance of a point from the line through `a` and `b`.
+fn distance_to_line(point: Point2<f32>, a: Point2<f32>, b: Point2<f32>) -> f32 {
+    let length = distance(a, b);
+
+    if length == 0.0 {
+        return distance(a, point);
+    }
+
+    ((b.x - a.x) * (a.y - point.y) - (a.x - point.x) * (b.y - a.y)).abs() / length
+}
+
+/// Returns the real roots of `a * t^2 + b * t + c`.
+fn solve_quadratic(a: f32, b: f32, c: f32) -> [Option<f32>; 2] {
+    if a.abs() < 1e-12 {
+        return [(b != 0.0).then(|| -c / b), None];
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+
+    let root = discriminant.sqrt();
+
+    [Some((-b + root) / (2.0 * a)), Some((-b - root) / (2.0 * a))]
+}
+
+pub(super) fn lerp(a: Point2<f32>, b: Point2<f32>, t: f32) -> Point2<f32> {
+    Point2::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+}
+
+pub(super) fn sub(a: Point2<f32>, b: Point2<f32>) -> Vec2<f32> {
+    Vec2::new(a.x - b.x, a.y - b.y)
+}
+
+pub(super) fn scale(v: Vec2<f32>, s: f32) -> Vec2<f32> {
+    Vec2::new(v.x * s, v.y * s)
+}
+
+pub(super) fn distance(a: Point2<f32>, b: Point2<f32>) -> f32 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
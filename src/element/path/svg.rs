@@ -0,0 +1,428 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::{Path, PathBuilder, PathElement, QUARTER_TURNS};
+use crate::math::{Point2, Vec2};
+
+/// Error when parsing SVG path data.
+///
+/// Positions are byte offsets into the path data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum PathDataError {
+    /// The path data doesn't start with a move command.
+    #[error("expected a move command at {position}")]
+    ExpectedMove { position: usize },
+    /// A number was expected.
+    #[error("expected a number at {position}")]
+    ExpectedNumber { position: usize },
+    /// An arc flag (`0` or `1`) was expected.
+    #[error("expected a flag at {position}")]
+    ExpectedFlag { position: usize },
+    /// A character that isn't part of the path data syntax was found.
+    #[error("unexpected character `{found}` at {position}")]
+    UnexpectedCharacter { position: usize, found: char },
+}
+
+impl PathDataError {
+    /// The byte offset of the error into the path data.
+    pub fn position(&self) -> usize {
+        match *self {
+            Self::ExpectedMove { position }
+            | Self::ExpectedNumber { position }
+            | Self::ExpectedFlag { position }
+            | Self::UnexpectedCharacter { position, .. } => position,
+        }
+    }
+}
+
+impl Path {
+    /// Parses SVG path data (the `d` attribute of a `<path>`).
+    ///
+    /// All commands are supported, both absolute and relative. Arcs are
+    /// approximated with a cubic segment per quarter turn, which is accurate to
+    /// ~0.03% of the radius (see [`Path::from_svg_path_data_with_tolerance`]).
+    pub fn from_svg_path_data(data: &str) -> Result<Self, PathDataError> {
+        Self::from_svg_path_data_with_tolerance(data, QUARTER_TURNS)
+    }
+
+    /// Parses SVG path data (the `d` attribute of a `<path>`), approximating
+    /// arcs with cubic segments that deviate from them by at most `tolerance`.
+    pub fn from_svg_path_data_with_tolerance(
+        data: &str,
+        tolerance: f32,
+    ) -> Result<Self, PathDataError> {
+        let mut parser = Parser { data, position: 0 };
+        let mut builder = PathBuilder::new();
+
+        let mut command = None;
+        let mut current = Point2::new(0.0, 0.0);
+        // the start of the current subpath
+        let mut start = current;
+        // the last control point of the previous segment, used to reflect the first
+        // control point of smooth segments
+        let mut last_cubic = None;
+        let mut last_quad = None;
+
+        while let Some(byte) = parser.peek() {
+            let position = parser.position;
+
+            if byte.is_ascii_alphabetic() {
+                if !b"MmLlHhVvCcSsQqTtAaZz".contains(&byte) {
+                    return Err(parser.unexpected());
+                }
+                if command.is_none() && !matches!(byte, b'M' | b'm') {
+                    return Err(PathDataError::ExpectedMove { position });
+                }
+
+                parser.position += 1;
+                command = Some(byte);
+            } else {
+                match command {
+                    None => return Err(PathDataError::ExpectedMove { position }),
+                    // numbers can't follow a close
+                    Some(b'Z' | b'z') => return Err(parser.unexpected()),
+                    // the command is repeated
+                    Some(_) => {},
+                }
+            }
+
+            let command = command.as_mut().unwrap();
+            let relative = command.is_ascii_lowercase();
+            let offset = if relative { current.to_vec() } else { Vec2::new(0.0, 0.0) };
+
+            let (mut next_cubic, mut next_quad) = (None, None);
+
+            match command.to_ascii_uppercase() {
+                b'M' => {
+                    current = parser.point()? + offset;
+                    start = current;
+
+                    builder.open(current);
+
+                    // following coordinates are implicit lines
+                    *command = if relative { b'l' } else { b'L' };
+                },
+                b'L' => {
+                    current = parser.point()? + offset;
+
+                    builder.line_to(current);
+                },
+                b'H' => {
+                    current.x = parser.number()? + offset.x;
+
+                    builder.line_to(current);
+                },
+                b'V' => {
+                    current.y = parser.number()? + offset.y;
+
+                    builder.line_to(current);
+                },
+                b'C' | b'S' => {
+                    let c1 = if *command == b'C' || *command == b'c' {
+                        parser.point()? + offset
+                    } else {
+                        last_cubic.map_or(current, |c: Point2<f32>| reflect(c, current))
+                    };
+                    let c2 = parser.point()? + offset;
+
+                    current = parser.point()? + offset;
+                    next_cubic = Some(c2);
+
+                    builder.cubic_to(c1, c2, current);
+                },
+                b'Q' | b'T' => {
+                    let c = if *command == b'Q' || *command == b'q' {
+                        parser.point()? + offset
+                    } else {
+                        last_quad.map_or(current, |c: Point2<f32>| reflect(c, current))
+                    };
+
+                    current = parser.point()? + offset;
+                    next_quad = Some(c);
+
+                    builder.quad_to(c, current);
+                },
+                b'A' => {
+                    let radii = Vec2::new(parser.number()?, parser.number()?);
+                    let x_rotation = parser.number()?.to_radians();
+                    let large_arc = parser.flag()?;
+                    let sweep = parser.flag()?;
+                    let to = parser.point()? + offset;
+
+                    builder.svg_arc_to(radii, x_rotation, large_arc, sweep, to, tolerance);
+
+                    current = to;
+                },
+                b'Z' => {
+                    current = start;
+
+                    builder.close();
+                },
+                _ => unreachable!("commands are validated when parsed"),
+            }
+
+            last_cubic = next_cubic;
+            last_quad = next_quad;
+
+            parser.skip_separators();
+        }
+
+        // subpaths without a `Z` are open
+        Ok(builder.build_open())
+    }
+
+    /// Formats this path as SVG path data.
+    ///
+    /// All commands are absolute.
+    pub fn to_svg_path_data(&self) -> String {
+        let mut out = String::new();
+
+        for (i, element) in self.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+
+            _ = match element {
+                PathElement::Open(p) => write!(out, "M{} {}", p.x, p.y),
+                PathElement::LineTo(p) => write!(out, "L{} {}", p.x, p.y),
+                PathElement::QuadTo { p, c } => write!(out, "Q{} {} {} {}", p.x, p.y, c.x, c.y),
+                PathElement::CubicTo { p, c1, c2 } => {
+                    write!(out, "C{} {} {} {} {} {}", p.x, p.y, c1.x, c1.y, c2.x, c2.y)
+                },
+                PathElement::Close => write!(out, "Z"),
+            };
+        }
+
+        out
+    }
+}
+
+impl FromStr for Path {
+    type Err = PathDataError;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        Self::from_svg_path_data(data)
+    }
+}
+
+/// Reflects a control point about a point.
+fn reflect(control: Point2<f32>, about: Point2<f32>) -> Point2<f32> {
+    Point2::new(2.0 * about.x - control.x, 2.0 * about.y - control.y)
+}
+
+struct Parser<'a> {
+    data: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    /// Skips whitespace and commas, then returns the next byte.
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+
+        self.data.as_bytes().get(self.position).copied()
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r' | b'\x0c' | b',') =
+            self.data.as_bytes().get(self.position)
+        {
+            self.position += 1;
+        }
+    }
+
+    fn point(&mut self) -> Result<Point2<f32>, PathDataError> {
+        Ok(Point2::new(self.number()?, self.number()?))
+    }
+
+    fn number(&mut self) -> Result<f32, PathDataError> {
+        self.skip_separators();
+
+        let bytes = self.data.as_bytes();
+        let start = self.position;
+        let mut end = start;
+
+        let digits = |end: &mut usize| {
+            let start = *end;
+
+            while bytes.get(*end).is_some_and(u8::is_ascii_digit) {
+                *end += 1;
+            }
+
+            *end > start
+        };
+
+        if let Some(b'+' | b'-') = bytes.get(end) {
+            end += 1;
+        }
+
+        let mut has_digits = digits(&mut end);
+
+        if bytes.get(end) == Some(&b'.') {
+            end += 1;
+            has_digits |= digits(&mut end);
+        }
+
+        if !has_digits {
+            return Err(PathDataError::ExpectedNumber { position: start });
+        }
+
+        // only consume an exponent if it is complete
+        if let Some(b'e' | b'E') = bytes.get(end) {
+            let mut exponent = end + 1;
+
+            if let Some(b'+' | b'-') = bytes.get(exponent) {
+                exponent += 1;
+            }
+
+            if digits(&mut exponent) {
+                end = exponent;
+            }
+        }
+
+        self.position = end;
+
+        self.data[start..end].parse().map_err(|_| PathDataError::ExpectedNumber { position: start })
+    }
+
+    fn flag(&mut self) -> Result<bool, PathDataError> {
+        self.skip_separators();
+
+        let flag = match self.data.as_bytes().get(self.position) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(PathDataError::ExpectedFlag { position: self.position }),
+        };
+
+        self.position += 1;
+
+        Ok(flag)
+    }
+
+    /// Returns an error for the character at the current position.
+    fn unexpected(&self) -> PathDataError {
+        let found = self.data[self.position..].chars().next().unwrap_or_default();
+
+        PathDataError::UnexpectedCharacter { position: self.position, found }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f32, y: f32) -> Point2<f32> {
+        Point2::new(x, y)
+    }
+
+    #[test]
+    fn absolute_and_relative_commands_are_equivalent() {
+        let absolute = Path::from_svg_path_data("M10 10 L20 10 H30 V20 Q40 20 40 30 Z").unwrap();
+        let relative = Path::from_svg_path_data("m10,10 l10,0 h10 v10 q10,0 10,10 z").unwrap();
+
+        assert_eq!(absolute, relative);
+        assert_eq!(absolute.to_svg_path_data(), "M10 10 L20 10 L30 10 L30 20 Q40 20 40 30 Z");
+    }
+
+    #[test]
+    fn open_subpaths_stay_open() {
+        for data in ["M0 0 L10 0 L10 10", "M0 0 L10 0 Z M20 0 L30 0"] {
+            assert_eq!(Path::from_svg_path_data(data).unwrap().to_svg_path_data(), data);
+        }
+    }
+
+    #[test]
+    fn implicit_commands_and_compact_numbers_are_parsed() {
+        let path: Path = "M0,0 10-5.5.5 1e1Z".parse().unwrap();
+
+        assert_eq!(
+            path.iter().collect::<Vec<_>>(),
+            [
+                PathElement::Open(p(0.0, 0.0)),
+                PathElement::LineTo(p(10.0, -5.5)),
+                PathElement::LineTo(p(0.5, 10.0)),
+                PathElement::Close,
+            ],
+        );
+    }
+
+    #[test]
+    fn smooth_segments_reflect_control_points() {
+        let path = Path::from_svg_path_data("M0 0 C0 10 10 10 10 0 S20 -10 20 0 T30 0 Z").unwrap();
+        let elements: Vec<_> = path.iter().collect();
+
+        assert_eq!(
+            elements[2],
+            PathElement::CubicTo { p: p(10.0, -10.0), c1: p(20.0, -10.0), c2: p(20.0, 0.0) }
+        );
+        // the previous segment is a cubic, so the control point is the current point
+        assert_eq!(elements[3], PathElement::QuadTo { p: p(20.0, 0.0), c: p(30.0, 0.0) });
+    }
+
+    #[test]
+    fn arcs_end_at_their_endpoint() {
+        let path = Path::from_svg_path_data("M0 0 A10 10 0 0 1 20 0 a5 5 0 1 0 -10 0").unwrap();
+        let ends: Vec<_> = path
+            .iter()
+            .filter_map(|element| match element {
+                PathElement::CubicTo { c2, .. } => Some(c2),
+                _ => None,
+            })
+            .collect();
+
+        // a half circle is split into 2 segments
+        assert_eq!(ends.len(), 4);
+        assert!((ends[1].x - 20.0).abs() < 1e-4 && ends[1].y.abs() < 1e-4);
+        assert!((ends[3].x - 10.0).abs() < 1e-4 && ends[3].y.abs() < 1e-4);
+        // sweeping clockwise (`y`-down) from the left goes up
+        assert!(ends[0].y < -9.9);
+    }
+
+    #[test]
+    fn arcs_are_within_the_tolerance() {
+        let data = "M0 0 A100 100 0 0 1 200 0";
+        let path = Path::from_svg_path_data_with_tolerance(data, 0.01).unwrap();
+        let cubics: Vec<_> = path
+            .iter()
+            .filter_map(|element| match element {
+                PathElement::CubicTo { p, c1, c2 } => Some([p, c1, c2]),
+                _ => None,
+            })
+            .collect();
+
+        // rather than a segment per quarter turn
+        assert!(cubics.len() > 2);
+
+        let mut start = p(0.0, 0.0);
+
+        for [p1, p2, end] in cubics {
+            // the midpoint of a cubic segment
+            let mid = |f: fn(Point2<f32>) -> f32| (f(start) + 3.0 * (f(p1) + f(p2)) + f(end)) / 8.0;
+
+            assert!(((mid(|p| p.x) - 100.0).hypot(mid(|p| p.y)) - 100.0).abs() < 0.01);
+            start = end;
+        }
+    }
+
+    #[test]
+    fn errors_have_positions() {
+        assert_eq!(
+            Path::from_svg_path_data("L0 0"),
+            Err(PathDataError::ExpectedMove { position: 0 })
+        );
+        assert_eq!(
+            Path::from_svg_path_data("M0 0 L1"),
+            Err(PathDataError::ExpectedNumber { position: 7 }),
+        );
+        assert_eq!(
+            Path::from_svg_path_data("M0 0 A1 1 0 2 0 1 1"),
+            Err(PathDataError::ExpectedFlag { position: 12 }),
+        );
+        assert_eq!(
+            Path::from_svg_path_data("M0 0 X"),
+            Err(PathDataError::UnexpectedCharacter { position: 5, found: 'X' }),
+        );
+    }
+}
@@ -56,7 +56,7 @@ impl Source {
                 .iter()
                 .map(|Span { source, .. }| source)
                 .fold(String::new(), |mut acc, span| {
-                    acc.push_str(&span);
+                    acc.push_str(span);
 
                     acc
                 })
@@ -74,7 +74,7 @@ impl<'a> SourceRef<'a> {
                 .iter()
                 .map(|Span { source, .. }| source)
                 .fold(String::new(), |mut acc, span| {
-                    acc.push_str(&span);
+                    acc.push_str(span);
 
                     acc
                 })
@@ -1,8 +1,14 @@
+use std::f32::consts::SQRT_2;
+use std::sync::Arc;
+
 #[cfg(feature = "renderer")]
 use vello::kurbo;
 
 /// Describes the style of a stroke operation.
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Unlike the other styles, this isn't `Copy`, since it shares its dash array.
+/// Cloning it is cheap.
+#[derive(Debug, Clone, PartialEq)]
 pub struct StrokeStyle {
     /// The width of the stroke.
     pub width: f32,
@@ -14,6 +20,12 @@ pub struct StrokeStyle {
     pub end: Cap,
     /// The miter limit for [`Join::Miter`].
     pub miter_limit: f32,
+    /// Alternating lengths of dashes and gaps, or `None` for a solid stroke.
+    ///
+    /// Like SVG, an odd amount of lengths is repeated to make it even.
+    pub dash_array: Option<Arc<[f32]>>,
+    /// How far into the dash array the stroke starts.
+    pub dash_offset: f32,
 }
 
 /// Describes how to join segments of a stroke.
@@ -48,14 +60,87 @@ impl Default for StrokeStyle {
             start: Default::default(),
             end: Default::default(),
             miter_limit: 4.0,
+            dash_array: None,
+            dash_offset: 0.0,
         }
     }
 }
 
+impl StrokeStyle {
+    /// Returns a solid stroke style with a width.
+    pub fn new(width: f32) -> Self {
+        Self { width, ..Default::default() }
+    }
+
+    /// Returns this style with a new width.
+    pub fn with_width(self, width: f32) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Returns this style with a new join.
+    pub fn with_join(self, join: Join) -> Self {
+        Self { join, ..self }
+    }
+
+    /// Returns this style with the same cap on both ends.
+    pub fn with_cap(self, cap: Cap) -> Self {
+        Self { start: cap, end: cap, ..self }
+    }
+
+    /// Returns this style with a new miter limit.
+    pub fn with_miter_limit(self, miter_limit: f32) -> Self {
+        Self { miter_limit, ..self }
+    }
+
+    /// Returns this style with a dash array.
+    ///
+    /// An empty dash array, or one without a positive length, is a solid
+    /// stroke.
+    pub fn with_dash_array(self, dash_array: impl Into<Arc<[f32]>>) -> Self {
+        let dash_array: Arc<[f32]> = dash_array.into();
+        let dash_array = dash_array.iter().any(|&length| length > 0.0).then_some(dash_array);
+
+        Self { dash_array, ..self }
+    }
+
+    /// Returns this style with a new dash offset.
+    pub fn with_dash_offset(self, dash_offset: f32) -> Self {
+        Self { dash_offset, ..self }
+    }
+
+    /// Returns how far joins and caps can extend from the stroked path.
+    pub(crate) fn reach(&self) -> f32 {
+        self.width.abs() / 2.0 * self.miter_limit.max(SQRT_2)
+    }
+
+    /// Returns the dash array with an even amount of lengths, or `None` for a
+    /// solid stroke.
+    pub fn dashes(&self) -> Option<impl Iterator<Item = f32> + Clone + '_> {
+        let dash_array = self.dash_array.as_deref()?;
+        let repeat = if dash_array.len() % 2 == 0 { 1 } else { 2 };
+
+        dash_array
+            .iter()
+            .any(|&length| length > 0.0)
+            .then(|| dash_array.iter().copied().cycle().take(repeat * dash_array.len()))
+    }
+}
+
 #[cfg(feature = "renderer")]
 impl From<StrokeStyle> for kurbo::Stroke {
     fn from(stroke_style: StrokeStyle) -> Self {
-        let StrokeStyle { width, join, start, end, miter_limit } = stroke_style;
+        (&stroke_style).into()
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl From<&StrokeStyle> for kurbo::Stroke {
+    fn from(stroke_style: &StrokeStyle) -> Self {
+        let dash_pattern = stroke_style
+            .dashes()
+            .map(|dashes| dashes.map(|length| length.max(0.0) as _).collect())
+            .unwrap_or_default();
+        let StrokeStyle { width, join, start, end, miter_limit, dash_offset, .. } = *stroke_style;
 
         kurbo::Stroke {
             width: width as _,
@@ -63,8 +148,8 @@ impl From<StrokeStyle> for kurbo::Stroke {
             miter_limit: miter_limit as _,
             start_cap: start.into(),
             end_cap: end.into(),
-            dash_pattern: kurbo::Dashes::new(),
-            dash_offset: 0.0,
+            dash_pattern,
+            dash_offset: dash_offset as _,
         }
     }
 }
@@ -90,3 +175,17 @@ impl From<Cap> for kurbo::Cap {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odd_dash_arrays_are_repeated() {
+        let style = StrokeStyle::new(2.0).with_dash_array([4.0, 2.0, 1.0]);
+
+        assert_eq!(style.dashes().unwrap().collect::<Vec<_>>(), [4.0, 2.0, 1.0, 4.0, 2.0, 1.0]);
+        assert!(StrokeStyle::new(2.0).with_dash_array([0.0, 0.0]).dash_array.is_none());
+        assert!(StrokeStyle::default().with_dash_array(Vec::new()).dashes().is_none());
+    }
+}
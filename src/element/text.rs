@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 use parley::fontique;
 
 use super::Color;
@@ -76,7 +76,7 @@ impl Default for TextStyle {
     }
 }
 
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 impl<'a> From<&'a FontFamily> for parley::style::FontFamily<'a> {
     fn from(font_family: &'a FontFamily) -> Self {
         use parley::style::GenericFamily;
@@ -90,7 +90,7 @@ impl<'a> From<&'a FontFamily> for parley::style::FontFamily<'a> {
     }
 }
 
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 impl From<FontStyle> for parley::style::FontStyle {
     fn from(font_style: FontStyle) -> Self {
         match font_style {
@@ -123,14 +123,14 @@ impl Default for FontWeight {
     }
 }
 
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 impl From<FontWeight> for fontique::Weight {
     fn from(font_weight: FontWeight) -> Self {
         fontique::Weight::new(font_weight.0)
     }
 }
 
-#[cfg(feature = "renderer")]
+#[cfg(feature = "text")]
 impl From<TextAlignment> for parley::layout::Alignment {
     fn from(text_alignment: TextAlignment) -> Self {
         match text_alignment {
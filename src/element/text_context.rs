@@ -1,6 +1,5 @@
 use parley::fontique::{Collection, CollectionOptions};
 use parley::{FontContext, LayoutContext};
-use vello::peniko;
 
 /// State used to measure and render text.
 pub struct TextContext {
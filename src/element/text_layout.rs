@@ -2,11 +2,10 @@ use std::{fmt, mem};
 
 use parley::style::{FontStack, StyleProperty};
 use parley::Layout;
-use vello::glyph::skrifa::prelude::NormalizedCoord;
-use vello::peniko;
+use skrifa::prelude::NormalizedCoord;
 
-use super::{Brush, SourceRef, TextAlignment, TextContext, TextStyle};
-use crate::math::{Affine2, Point2, Size2};
+use super::{Brush, Color, Path, PathBuilder, SourceRef, TextAlignment, TextContext, TextStyle};
+use crate::math::{Point2, Size2, Vec2};
 
 /// Precalculated layout of some text.
 #[derive(Clone)]
@@ -14,6 +13,30 @@ pub struct TextLayout {
     inner: Layout<peniko::Brush>,
 }
 
+/// A sequence of glyphs in a [`TextLayout`] that share a font and style.
+#[derive(Debug, Clone)]
+pub struct GlyphRun {
+    /// The font of the glyphs.
+    pub font: peniko::Font,
+    /// The size of the font.
+    pub font_size: f32,
+    /// The normalized variation coordinates of the font.
+    pub normalized_coords: Vec<i16>,
+    /// The glyphs in this run.
+    pub glyphs: Vec<Glyph>,
+    /// The brush to fill the glyphs with.
+    pub brush: Brush,
+}
+
+/// A positioned glyph in a [`GlyphRun`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    /// The id of the glyph within its font.
+    pub id: u32,
+    /// The position of the glyph's origin on the baseline.
+    pub position: Point2<f32>,
+}
+
 impl TextLayout {
     /// Creates an empty layout.
     pub fn new() -> Self {
@@ -42,7 +65,7 @@ impl TextLayout {
         let source = source.into();
         let text = source.text();
 
-        let brush: peniko::Brush = Brush::Solid(style.color).into();
+        let brush = peniko::Brush::Solid(style.color.into());
         let size = style.size;
 
         let font_family: parley::style::FontFamily = (&style.font.family).into();
@@ -53,7 +76,7 @@ impl TextLayout {
 
         let mut font_stack: Vec<parley::style::FontFamily> = Vec::new();
 
-        font_stack.push(font_family.into());
+        font_stack.push(font_family);
         font_stack.extend(style.font.fallback.iter().map(parley::style::FontFamily::from));
 
         builder.push_default(&StyleProperty::FontStack(FontStack::List(&font_stack)));
@@ -85,9 +108,9 @@ impl TextLayout {
                     builder.push(&StyleProperty::FontWeight(font_weight.into()), range.clone());
                 }
                 if let Some(color) = span.color {
-                    let brush: Brush = color.into();
+                    let brush = peniko::Brush::Solid(color.into());
 
-                    builder.push(&StyleProperty::Brush(brush.into()), range.clone());
+                    builder.push(&StyleProperty::Brush(brush), range.clone());
                 }
                 if let Some(size) = span.size {
                     builder.push(&StyleProperty::FontSize(size), range);
@@ -101,42 +124,124 @@ impl TextLayout {
         self.break_lines(f32::MAX, style.alignment);
     }
 
-    pub(crate) fn render(&self, origin: Point2<f32>, output: &mut vello::Scene) {
-        for line in self.inner.lines() {
-            for glyph_run in line.glyph_runs() {
+    /// Returns the glyph runs in this layout, positioned relative to `origin`.
+    pub fn glyph_runs(&self, origin: Point2<f32>) -> impl Iterator<Item = GlyphRun> + '_ {
+        self.inner.lines().flat_map(move |line| {
+            line.glyph_runs().map(move |glyph_run| {
                 let run = glyph_run.run();
 
                 let mut run_x = glyph_run.offset();
                 let run_y = glyph_run.baseline();
 
-                let coords: Vec<_> = run
-                    .normalized_coords()
-                    .iter()
-                    .copied()
-                    .map(NormalizedCoord::from_bits)
+                let glyphs = glyph_run
+                    .glyphs()
+                    .map(|parley::layout::Glyph { id, x, y, advance, .. }| {
+                        let position = origin + Vec2::new(x + run_x, y + run_y);
+
+                        run_x += advance;
+
+                        Glyph { id: id as _, position }
+                    })
                     .collect();
+                let brush = match &glyph_run.style().brush {
+                    peniko::Brush::Solid(color) => Color::rgba(
+                        color.r as f32 / 255.0,
+                        color.g as f32 / 255.0,
+                        color.b as f32 / 255.0,
+                        color.a as f32 / 255.0,
+                    )
+                    .into(),
+                    // text is only ever styled with solid colors
+                    _ => unreachable!(),
+                };
+
+                GlyphRun {
+                    font: run.font().clone(),
+                    font_size: run.font_size(),
+                    normalized_coords: run.normalized_coords().to_vec(),
+                    glyphs,
+                    brush,
+                }
+            })
+        })
+    }
+}
 
-                output
-                    .draw_glyphs(run.font())
-                    .brush(&glyph_run.style().brush)
-                    .transform(Affine2::from_translation(origin.to_vec()).into())
-                    .font_size(run.font_size())
-                    .normalized_coords(&coords)
-                    .draw(
-                        peniko::Fill::NonZero,
-                        glyph_run.glyphs().map(
-                            |parley::layout::Glyph { id, x, y, advance, .. }| {
-                                let out =
-                                    vello::glyph::Glyph { id: id as _, x: x + run_x, y: y + run_y };
-
-                                run_x += advance;
-
-                                out
-                            },
-                        ),
-                    );
+impl GlyphRun {
+    /// Returns the outlines of the glyphs in this run.
+    pub fn outline(&self) -> Path {
+        use skrifa::instance::{LocationRef, Size};
+        use skrifa::outline::{DrawSettings, OutlinePen};
+        use skrifa::{FontRef, GlyphId, MetadataProvider};
+
+        /// Writes glyph outlines to a path, flipping them to be `y`-down.
+        struct Pen<'a> {
+            builder: &'a mut PathBuilder,
+            offset: Point2<f32>,
+        }
+
+        impl Pen<'_> {
+            fn point(&self, x: f32, y: f32) -> Point2<f32> {
+                Point2::new(self.offset.x + x, self.offset.y - y)
+            }
+        }
+
+        impl OutlinePen for Pen<'_> {
+            fn move_to(&mut self, x: f32, y: f32) {
+                let p = self.point(x, y);
+
+                self.builder.open(p);
+            }
+
+            fn line_to(&mut self, x: f32, y: f32) {
+                let p = self.point(x, y);
+
+                self.builder.line_to(p);
+            }
+
+            fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+                let (c, p) = (self.point(cx0, cy0), self.point(x, y));
+
+                self.builder.quad_to(c, p);
+            }
+
+            fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+                let (c0, c1, p) = (self.point(cx0, cy0), self.point(cx1, cy1), self.point(x, y));
+
+                self.builder.cubic_to(c0, c1, p);
+            }
+
+            fn close(&mut self) {
+                self.builder.close();
             }
         }
+
+        let mut builder = PathBuilder::new();
+
+        let Ok(font) = FontRef::from_index(self.font.data.as_ref(), self.font.index) else {
+            return builder.build();
+        };
+        let outlines = font.outline_glyphs();
+        let coords: Vec<_> =
+            self.normalized_coords.iter().copied().map(NormalizedCoord::from_bits).collect();
+
+        for &Glyph { id, position } in &self.glyphs {
+            let Some(glyph) = outlines.get(GlyphId::new(id as _)) else {
+                continue;
+            };
+            let settings =
+                DrawSettings::unhinted(Size::new(self.font_size), LocationRef::new(&coords));
+
+            _ = glyph.draw(settings, &mut Pen { builder: &mut builder, offset: position });
+        }
+
+        builder.build()
+    }
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
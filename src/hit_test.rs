@@ -0,0 +1,141 @@
+use crate::element::{FillRule, Layer};
+use crate::math::{Affine2, Point2};
+use crate::util::max_scale;
+use crate::{Command, Scene};
+
+/// The most that flattened strokes deviate from their curves, in scene units.
+const TOLERANCE: f32 = 0.1;
+
+/// An open layer while hit testing.
+struct HitLayer {
+    /// The transform from the layer to the scene.
+    transform: Affine2<f32>,
+    /// The point in the space of the layer, or `None` if it's clipped out.
+    point: Option<Point2<f32>>,
+}
+
+impl Scene {
+    /// Returns an iterator of the indices of the commands drawn under a point.
+    ///
+    /// Commands are visited back-to-front, so the last index is of the
+    /// topmost command. Fills and strokes are tested against their geometry,
+    /// and text and images against their bounds. Layer transforms and clips
+    /// are respected, but alpha and blend modes aren't. Curved strokes are
+    /// flattened to within `0.1` units of the scene, however layers are
+    /// transformed.
+    pub fn hit_test(&self, point: Point2<f32>) -> impl Iterator<Item = usize> + '_ {
+        let mut layers = vec![HitLayer { transform: Affine2::IDENTITY, point: Some(point) }];
+
+        self.commands.iter().enumerate().filter_map(move |(i, command)| {
+            let HitLayer { transform, point: local } = *layers.last()?;
+            // strokes are flattened in the space of the layer, finely enough for the
+            // direction it's stretched the most in
+            let tolerance = TOLERANCE / max_scale(transform.transform);
+
+            let hit = match command {
+                Command::Fill { path, style, .. } => {
+                    local.is_some_and(|local| path.contains(local, style.rule))
+                },
+                Command::Stroke { path, style, .. } => {
+                    local.is_some_and(|local| path.hit_stroke(local, style, tolerance))
+                },
+                Command::DrawText { bounds, .. } | Command::DrawImage { bounds, .. } => {
+                    local.is_some_and(|local| bounds.contains_point(local))
+                },
+                #[cfg(feature = "text")]
+                Command::DrawTextLayout { layout, origin } => local.is_some_and(|local| {
+                    crate::math::Rect::new(*origin, layout.size()).contains_point(local)
+                }),
+                Command::PushLayer(Layer { transform: layer_transform, clip, .. }) => {
+                    let transform = transform * *layer_transform;
+                    // layers that can't be inverted have no area
+                    let point = local
+                        .and(transform.inverse())
+                        .map(|inverse| inverse.transform_point(point))
+                        .filter(|&local| {
+                            clip.as_ref()
+                                .map_or(true, |clip| clip.contains(local, FillRule::NonZero))
+                        });
+
+                    layers.push(HitLayer { transform, point });
+
+                    false
+                },
+                Command::PopLayer => {
+                    // the root layer is never popped
+                    if layers.len() > 1 {
+                        layers.pop();
+                    }
+
+                    false
+                },
+            };
+
+            hit.then_some(i)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{Color, FillStyle, Image, ImageQuality, Path, StrokeStyle, TextStyle};
+    use crate::math::{Circle, Line, Rect, Size2, Vec2};
+
+    fn layer(transform: Affine2<f32>, clip: Option<Path>) -> Layer {
+        Layer { transform, blend_mode: Default::default(), clip, alpha: 1.0 }
+    }
+
+    fn hits(scene: &Scene, x: f32, y: f32) -> Vec<usize> {
+        scene.hit_test(Point2::new(x, y)).collect()
+    }
+
+    #[test]
+    fn fills_strokes_and_bounds() {
+        let mut scene = Scene::new();
+        let bounds = Rect::new(Point2::new(0.0, 0.0), Size2::splat(10.0));
+
+        scene.fill(Circle::new(Point2::new(5.0, 5.0), 5.0), Color::BLACK, FillStyle::default());
+        scene.stroke(
+            Line::new(Point2::new(0.0, 0.0), Point2::new(10.0, 10.0)),
+            Color::WHITE,
+            StrokeStyle::new(2.0),
+        );
+        scene.draw_text("text", bounds, TextStyle::default());
+        scene.draw_image(Image::new(vec![0; 4], 1, 1).unwrap(), bounds, ImageQuality::Low);
+
+        assert_eq!(hits(&scene, 5.0, 5.0), [0, 1, 2, 3]);
+        assert_eq!(hits(&scene, 0.5, 0.5), [1, 2, 3]);
+        assert_eq!(hits(&scene, 8.0, 3.0), [0, 2, 3]);
+        assert!(hits(&scene, 11.0, 5.0).is_empty());
+    }
+
+    #[test]
+    fn layers_transform_and_clip() {
+        let mut scene = Scene::new();
+        let square = Rect::new(Point2::new(0.0, 0.0), Size2::splat(10.0));
+        let clip = Rect::new(Point2::new(0.0, 0.0), Size2::splat(5.0));
+        let transform = Affine2::from_scale(Vec2::splat(2.0)).then_translate(Vec2::new(100.0, 0.0));
+
+        scene.with_layer(layer(transform, Some(clip.into())), |scene| {
+            scene.fill(square, Color::BLACK, FillStyle::default());
+            scene.with_layer(layer(Affine2::IDENTITY, None), |scene| {
+                scene.fill(square, Color::BLACK, FillStyle::default());
+            });
+        });
+        scene.fill(square, Color::BLACK, FillStyle::default());
+
+        // the layer covers `100..120`, but is clipped to `100..110`
+        assert_eq!(hits(&scene, 105.0, 5.0), [1, 3]);
+        assert!(hits(&scene, 115.0, 5.0).is_empty());
+        assert_eq!(hits(&scene, 5.0, 5.0), [6]);
+
+        let mut degenerate = Scene::new();
+
+        degenerate.with_layer(layer(Affine2::from_scale(Vec2::splat(0.0)), None), |scene| {
+            scene.fill(square, Color::BLACK, FillStyle::default());
+        });
+
+        assert!(hits(&degenerate, 0.0, 0.0).is_empty());
+    }
+}
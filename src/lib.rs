@@ -4,30 +4,51 @@
 //!
 //! The API revolves around [`Scene`], a sequence of vector graphics
 //! [`Command`]s (fill, stroke, etc.). Scenes can be rendered with a
-//! [`Renderer`] (if the `renderer` feature is enabled).
+//! [`Renderer`] (if the `renderer` feature is enabled) or a `CpuRenderer`
+//! (if the `cpu` feature is enabled), or replayed into any other [`Backend`].
+//! They can also be converted to and from SVG documents (if the `svg` feature
+//! is enabled), and paths and scenes can be converted to triangle meshes
+//! with [`tessellate`].
 
-#![cfg_attr(doc, feature(doc_auto_cfg))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 
 use std::{slice, vec};
 
-use element::{Brush, FillStyle, Layer, Path, Source, StrokeStyle, TextLayout, TextStyle};
-use math::{Point2, Rect};
+#[cfg(feature = "text")]
+use element::TextLayout;
+use element::{Brush, FillStyle, Image, ImageQuality, Layer, Path, Source, StrokeStyle, TextStyle};
+#[cfg(feature = "text")]
+use math::Point2;
+use math::Rect;
+use thiserror::Error;
 #[cfg(feature = "renderer")]
 pub use vello::wgpu;
 
+pub use self::backend::*;
+#[cfg(feature = "cpu")]
+pub use self::cpu_renderer::*;
 #[cfg(feature = "renderer")]
 pub use self::renderer::*;
 
+mod backend;
+#[cfg(feature = "cpu")]
+mod cpu_renderer;
 pub mod element;
+mod hit_test;
 pub mod math;
 #[cfg(feature = "renderer")]
 mod renderer;
+#[cfg(feature = "svg")]
+pub mod svg;
+pub mod tessellate;
 mod util;
 
 /// A vector scene.
 #[derive(Debug, Clone)]
 pub struct Scene {
     commands: Vec<Command>,
+    /// The amount of layers that have been pushed but not yet popped.
+    depth: usize,
 }
 
 /// A single operation in a [`Scene`].
@@ -48,7 +69,12 @@ pub enum Command {
         bounds: Rect<f32>,
         style: TextStyle,
     },
-    #[cfg(feature = "renderer")]
+    DrawImage {
+        image: Image,
+        bounds: Rect<f32>,
+        quality: ImageQuality,
+    },
+    #[cfg(feature = "text")]
     DrawTextLayout {
         layout: TextLayout,
         origin: Point2<f32>,
@@ -59,12 +85,24 @@ pub enum Command {
     PopLayer,
 }
 
+/// Error when a [`Scene`] has invalid layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum SceneError {
+    /// [`Scene::pop_layer`] was called with no open layers.
+    #[error("attempted to pop a layer, but there are no open layers")]
+    NoOpenLayer,
+    /// Layers were pushed but never popped.
+    #[error("{0} layer(s) were pushed but never popped")]
+    UnclosedLayers(usize),
+}
+
 impl Scene {
     /// Creates a new empty scene.
     pub const fn new() -> Self {
         let commands = Vec::new();
+        let depth = 0;
 
-        Self { commands }
+        Self { commands, depth }
     }
 
     /// Returns the commands in this scene.
@@ -72,6 +110,11 @@ impl Scene {
         &self.commands
     }
 
+    /// Returns the amount of layers that are currently open.
+    pub fn layer_depth(&self) -> usize {
+        self.depth
+    }
+
     /// Encodes a fill operation.
     pub fn fill(&mut self, path: impl Into<Path>, brush: impl Into<Brush>, style: FillStyle) {
         self.commands.push(Command::Fill { path: path.into(), brush: brush.into(), style });
@@ -87,22 +130,69 @@ impl Scene {
         self.commands.push(Command::DrawText { source: source.into(), bounds, style });
     }
 
+    /// Draws an image scaled to fit within `bounds`.
+    pub fn draw_image(&mut self, image: Image, bounds: Rect<f32>, quality: ImageQuality) {
+        self.commands.push(Command::DrawImage { image, bounds, quality });
+    }
+
     /// Draws a [`TextLayout`].
+    #[cfg(feature = "text")]
     pub fn draw_text_layout(&mut self, layout: TextLayout, origin: Point2<f32>) {
         self.commands.push(Command::DrawTextLayout { layout, origin });
     }
 
-    // TODO: allow manual pushing and popping of layers by validating each operation
+    /// Pushes a new layer.
+    ///
+    /// All operations encoded until the matching call to [`Scene::pop_layer`]
+    /// will be within the layer.
+    pub fn push_layer(&mut self, layer: Layer) {
+        self.commands.push(Command::PushLayer(layer));
+        self.depth += 1;
+    }
+
+    /// Pops the most recently pushed layer.
+    ///
+    /// Returns an error if there are no open layers.
+    pub fn pop_layer(&mut self) -> Result<(), SceneError> {
+        if self.depth == 0 {
+            return Err(SceneError::NoOpenLayer);
+        }
+
+        self.commands.push(Command::PopLayer);
+        self.depth -= 1;
+
+        Ok(())
+    }
 
     /// Encodes a new layer.
     ///
-    /// All operations encoded in the closure will be within the layer.
+    /// All operations encoded in the closure will be within the layer. Layers
+    /// left open by the closure are popped along with it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the closure pops more layers than it pushes.
     pub fn with_layer(&mut self, layer: Layer, f: impl FnOnce(&mut Self)) {
-        self.commands.push(Command::PushLayer(layer));
+        let depth = self.depth;
+
+        self.push_layer(layer);
 
         f(self);
 
-        self.commands.push(Command::PopLayer);
+        assert!(self.depth > depth, "layer was popped within `Scene::with_layer`");
+
+        while self.depth > depth {
+            self.commands.push(Command::PopLayer);
+            self.depth -= 1;
+        }
+    }
+
+    /// Validates that every pushed layer has been popped.
+    pub fn finish(&self) -> Result<(), SceneError> {
+        match self.depth {
+            0 => Ok(()),
+            depth => Err(SceneError::UnclosedLayers(depth)),
+        }
     }
 
     /// Clears all commands from the scene.
@@ -110,6 +200,7 @@ impl Scene {
     /// Does not change the global transform.
     pub fn clear(&mut self) {
         self.commands.clear();
+        self.depth = 0;
     }
 }
 
@@ -136,3 +227,91 @@ impl IntoIterator for Scene {
         self.commands.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Affine2;
+
+    fn layer() -> Layer {
+        Layer {
+            transform: Affine2::IDENTITY,
+            blend_mode: Default::default(),
+            clip: None,
+            alpha: 1.0,
+        }
+    }
+
+    #[test]
+    fn pop_without_push_is_an_error() {
+        let mut scene = Scene::new();
+
+        assert_eq!(scene.pop_layer(), Err(SceneError::NoOpenLayer));
+        assert!(scene.commands().is_empty());
+    }
+
+    #[test]
+    fn balanced_layers_finish() {
+        let mut scene = Scene::new();
+
+        scene.push_layer(layer());
+        scene.with_layer(layer(), |scene| {
+            scene.push_layer(layer());
+            scene.pop_layer().unwrap();
+
+            assert_eq!(scene.layer_depth(), 2);
+        });
+
+        assert_eq!(scene.layer_depth(), 1);
+
+        scene.pop_layer().unwrap();
+
+        assert_eq!(scene.finish(), Ok(()));
+    }
+
+    #[test]
+    fn with_layer_pops_layers_left_open() {
+        let mut scene = Scene::new();
+
+        scene.with_layer(layer(), |scene| {
+            scene.push_layer(layer());
+            scene.push_layer(layer());
+        });
+
+        assert_eq!(scene.layer_depth(), 0);
+        assert_eq!(scene.finish(), Ok(()));
+        assert!(matches!(
+            scene.commands(),
+            [
+                Command::PushLayer(_),
+                Command::PushLayer(_),
+                Command::PushLayer(_),
+                Command::PopLayer,
+                Command::PopLayer,
+                Command::PopLayer,
+            ],
+        ));
+    }
+
+    #[test]
+    #[should_panic = "layer was popped within `Scene::with_layer`"]
+    fn with_layer_panics_if_its_layer_is_popped() {
+        let mut scene = Scene::new();
+
+        scene.with_layer(layer(), |scene| scene.pop_layer().unwrap());
+    }
+
+    #[test]
+    fn unclosed_layers_are_reported() {
+        let mut scene = Scene::new();
+
+        scene.push_layer(layer());
+        scene.push_layer(layer());
+
+        assert_eq!(scene.finish(), Err(SceneError::UnclosedLayers(2)));
+
+        scene.clear();
+
+        assert_eq!(scene.finish(), Ok(()));
+    }
+}
@@ -1,4 +1,6 @@
-use super::{Affine2, Mat2, One, Vec2, Zero};
+use std::ops::{Add, Div, Mul, MulAssign, Neg, Sub};
+
+use super::{Affine2, Mat2, One, Point2, Rect, Size2, Trig, Vec2, Zero};
 
 impl<T> Affine2<T> {
     /// Returns an affine transform for the given transform and translation.
@@ -32,6 +34,24 @@ impl<T> Affine2<T> {
         Self::new(Mat2::IDENTITY, translation)
     }
 
+    /// Returns a transform that rotates by an angle in radians, clockwise
+    /// (`y`-down).
+    pub fn from_rotation(angle: T) -> Self
+    where
+        T: Zero + Trig + Neg<Output = T>,
+    {
+        Self::from_transform(Mat2::from_rotation(angle))
+    }
+
+    /// Returns a transform that skews by angles in radians (see
+    /// [`Mat2::from_skew`]).
+    pub fn from_skew(skew: Vec2<T>) -> Self
+    where
+        T: Zero + One + Trig,
+    {
+        Self::from_transform(Mat2::from_skew(skew))
+    }
+
     /// Maps the transform portion of this transform.
     pub fn map_transform(self, f: impl FnOnce(Mat2<T>) -> Mat2<T>) -> Self {
         Self { transform: f(self.transform), ..self }
@@ -43,6 +63,132 @@ impl<T> Affine2<T> {
     }
 }
 
+impl<T> Affine2<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Returns the determinant of the transform portion of this transform.
+    ///
+    /// Its absolute value is the factor areas are scaled by.
+    pub fn determinant(self) -> T {
+        self.transform.determinant()
+    }
+
+    /// Returns the inverse of this transform, or `None` if it isn't invertible.
+    pub fn inverse(self) -> Option<Self>
+    where
+        T: Zero + PartialEq + Div<Output = T> + Neg<Output = T>,
+    {
+        let transform = self.transform.inverse()?;
+        let translation = transform * self.translation;
+
+        Some(Self::new(transform, Vec2::new(-translation.x, -translation.y)))
+    }
+
+    /// Applies this transform to a point.
+    pub fn transform_point(self, point: Point2<T>) -> Point2<T> {
+        (self.transform * point.to_vec() + self.translation).to_point()
+    }
+
+    /// Applies this transform to a vector, ignoring the translation.
+    pub fn transform_vec(self, vec: Vec2<T>) -> Vec2<T> {
+        self.transform * vec
+    }
+
+    /// Returns the smallest rectangle containing a rectangle after this
+    /// transform is applied to it.
+    pub fn transform_rect_bbox(self, rect: Rect<T>) -> Rect<T>
+    where
+        T: PartialOrd,
+    {
+        let [first, rest @ ..] = rect.corners().map(|corner| self.transform_point(corner));
+        let (mut min, mut max) = (first, first);
+
+        for corner in rest {
+            if corner.x < min.x {
+                min.x = corner.x;
+            }
+            if corner.y < min.y {
+                min.y = corner.y;
+            }
+            if corner.x > max.x {
+                max.x = corner.x;
+            }
+            if corner.y > max.y {
+                max.y = corner.y;
+            }
+        }
+
+        Rect::new(min, Size2::new(max.x - min.x, max.y - min.y))
+    }
+
+    /// Returns this transform with a translation applied after it.
+    pub fn then_translate(self, translation: Vec2<T>) -> Self {
+        Self { translation: self.translation + translation, ..self }
+    }
+
+    /// Returns this transform with a translation applied before it.
+    pub fn pre_translate(self, translation: Vec2<T>) -> Self
+    where
+        T: Zero + One,
+    {
+        self * Self::from_translation(translation)
+    }
+
+    /// Returns this transform with a scale applied after it.
+    pub fn then_scale(self, scale: Vec2<T>) -> Self
+    where
+        T: Zero,
+    {
+        Self::from_scale(scale) * self
+    }
+
+    /// Returns this transform with a scale applied before it.
+    pub fn pre_scale(self, scale: Vec2<T>) -> Self
+    where
+        T: Zero,
+    {
+        self * Self::from_scale(scale)
+    }
+
+    /// Returns this transform with a rotation in radians applied after it.
+    pub fn then_rotate(self, angle: T) -> Self
+    where
+        T: Zero + Trig + Neg<Output = T>,
+    {
+        Self::from_rotation(angle) * self
+    }
+
+    /// Returns this transform with a rotation in radians applied before it.
+    pub fn pre_rotate(self, angle: T) -> Self
+    where
+        T: Zero + Trig + Neg<Output = T>,
+    {
+        self * Self::from_rotation(angle)
+    }
+
+    /// Returns this transform with a skew in radians applied after it.
+    pub fn then_skew(self, skew: Vec2<T>) -> Self
+    where
+        T: Zero + One + Trig,
+    {
+        Self::from_skew(skew) * self
+    }
+
+    /// Returns this transform with a skew in radians applied before it.
+    pub fn pre_skew(self, skew: Vec2<T>) -> Self
+    where
+        T: Zero + One + Trig,
+    {
+        self * Self::from_skew(skew)
+    }
+
+    /// Returns this transform with another applied after it.
+    pub fn then(self, other: Self) -> Self {
+        other * self
+    }
+}
+
 impl<T: Zero + One> Affine2<T> {
     /// The identity transform.
     pub const IDENTITY: Self = Self::new(Mat2::IDENTITY, Vec2::ZERO);
@@ -55,6 +201,41 @@ impl<T: Zero + One> Default for Affine2<T> {
     }
 }
 
+/// Composes 2 transforms, applying `rhs` first.
+impl<T> Mul for Affine2<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.transform * rhs.transform,
+            self.transform * rhs.translation + self.translation,
+        )
+    }
+}
+
+impl<T> MulAssign for Affine2<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> Mul<Point2<T>> for Affine2<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Point2<T>;
+
+    fn mul(self, rhs: Point2<T>) -> Self::Output {
+        self.transform_point(rhs)
+    }
+}
+
 #[cfg(feature = "renderer")]
 impl From<Affine2<f32>> for vello::kurbo::Affine {
     fn from(transform: Affine2<f32>) -> Self {
@@ -65,3 +246,55 @@ impl From<Affine2<f32>> for vello::kurbo::Affine {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    fn assert_near(a: Point2<f32>, b: Point2<f32>) {
+        assert!((a.x - b.x).abs() < 1e-5 && (a.y - b.y).abs() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn composition_applies_the_right_hand_side_first() {
+        let transform = Affine2::from_translation(Vec2::new(10.0, 0.0)).then_rotate(FRAC_PI_2);
+
+        // translated to (11, 0), then rotated clockwise (`y`-down)
+        assert_near(transform * Point2::new(1.0, 0.0), Point2::new(0.0, 11.0));
+        assert_eq!(
+            transform,
+            Affine2::from_rotation(FRAC_PI_2) * Affine2::from_translation(Vec2::new(10.0, 0.0)),
+        );
+        assert_near(
+            Affine2::from_rotation(FRAC_PI_2).pre_translate(Vec2::new(10.0, 0.0))
+                * Point2::new(1.0, 0.0),
+            Point2::new(0.0, 11.0),
+        );
+    }
+
+    #[test]
+    fn inverse_undoes_the_transform() {
+        let transform = Affine2::from_scale(Vec2::new(2.0, 3.0))
+            .then_skew(Vec2::new(0.3, 0.0))
+            .then_rotate(1.0)
+            .then_translate(Vec2::new(5.0, -7.0));
+        let point = Point2::new(3.0, 4.0);
+
+        assert_near(transform.inverse().unwrap() * (transform * point), point);
+        assert!((transform.determinant() - 6.0).abs() < 1e-5);
+        assert_eq!(Affine2::from_scale(Vec2::new(0.0, 1.0)).inverse(), None);
+    }
+
+    #[test]
+    fn rect_bbox_contains_the_transformed_corners() {
+        let rect = Rect::new(Point2::new(0.0, 0.0), Size2::new(2.0, 2.0));
+        let bbox = Affine2::from_rotation(FRAC_PI_2 / 2.0).transform_rect_bbox(rect);
+        let half_diagonal = 2.0f32.sqrt();
+
+        assert_near(bbox.origin, Point2::new(-half_diagonal, 0.0));
+        assert!((bbox.size.w - 2.0 * half_diagonal).abs() < 1e-5);
+        assert!((bbox.size.h - 2.0 * half_diagonal).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,54 @@
+use std::f32::consts::TAU;
+
+use super::{Circle, Ellipse, Point2, Rect, Size2, Vec2};
+use crate::element::{Path, QUARTER_TURNS};
+
+impl<T> Circle<T> {
+    /// Returns a circle from its center and radius.
+    pub const fn new(center: Point2<T>, radius: T) -> Self {
+        Self { center, radius }
+    }
+}
+
+impl Circle<f32> {
+    /// Returns the smallest rectangle containing this circle.
+    pub fn bounds(self) -> Rect<f32> {
+        let Self { center, radius } = self;
+
+        Rect::new(center - Vec2::splat(radius), Size2::splat(2.0 * radius))
+    }
+
+    /// Returns a path of this circle, approximated with cubic segments that
+    /// deviate from it by at most `tolerance`.
+    ///
+    /// Converting into a [`Path`] uses a segment per quarter turn instead,
+    /// which is accurate to ~0.03% of the radius.
+    pub fn to_path(self, tolerance: f32) -> Path {
+        let Circle { center, radius } = self;
+
+        Path::from_fn(|builder| {
+            builder.arc_to(center, Vec2::splat(radius), 0.0, TAU, 0.0, tolerance);
+        })
+    }
+}
+
+impl<T: Copy + Default> From<Circle<T>> for Ellipse<T> {
+    fn from(circle: Circle<T>) -> Self {
+        Ellipse::new(circle.center, Vec2::splat(circle.radius), T::default())
+    }
+}
+
+impl From<Circle<f32>> for Path {
+    fn from(circle: Circle<f32>) -> Self {
+        circle.to_path(QUARTER_TURNS)
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl<T: Into<f64>> From<Circle<T>> for vello::kurbo::Circle {
+    fn from(circle: Circle<T>) -> Self {
+        let Circle { center: Point2 { x, y }, radius } = circle;
+
+        vello::kurbo::Circle::new((x.into(), y.into()), radius.into())
+    }
+}
@@ -0,0 +1,45 @@
+use std::f32::consts::TAU;
+
+use super::{Ellipse, Point2, Vec2};
+use crate::element::{Path, QUARTER_TURNS};
+
+impl<T> Ellipse<T> {
+    /// Returns an ellipse from its center, radii, and rotation in radians.
+    pub const fn new(center: Point2<T>, radii: Vec2<T>, rotation: T) -> Self {
+        Self { center, radii, rotation }
+    }
+}
+
+impl Ellipse<f32> {
+    /// Returns a path of this ellipse, approximated with cubic segments that
+    /// deviate from it by at most `tolerance`.
+    ///
+    /// Converting into a [`Path`] uses a segment per quarter turn instead,
+    /// which is accurate to ~0.03% of the larger radius.
+    pub fn to_path(self, tolerance: f32) -> Path {
+        let Ellipse { center, radii, rotation } = self;
+
+        Path::from_fn(|builder| {
+            builder.arc_to(center, radii, 0.0, TAU, rotation, tolerance);
+        })
+    }
+}
+
+impl From<Ellipse<f32>> for Path {
+    fn from(ellipse: Ellipse<f32>) -> Self {
+        ellipse.to_path(QUARTER_TURNS)
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl<T: Into<f64>> From<Ellipse<T>> for vello::kurbo::Ellipse {
+    fn from(ellipse: Ellipse<T>) -> Self {
+        let Ellipse { center: Point2 { x, y }, radii, rotation } = ellipse;
+
+        vello::kurbo::Ellipse::new(
+            (x.into(), y.into()),
+            (radii.x.into(), radii.y.into()),
+            rotation.into(),
+        )
+    }
+}
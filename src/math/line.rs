@@ -0,0 +1,82 @@
+use super::{Line, Point2};
+use crate::element::{Path, PathBuilder};
+
+impl<T> Line<T> {
+    /// Returns a line between 2 points.
+    pub const fn new(start: Point2<T>, end: Point2<T>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl Line<f32> {
+    /// Returns the length of this line.
+    pub fn length(self) -> f32 {
+        (self.end.x - self.start.x).hypot(self.end.y - self.start.y)
+    }
+
+    /// Returns the point halfway between the ends of this line.
+    pub fn midpoint(self) -> Point2<f32> {
+        Point2::new((self.start.x + self.end.x) / 2.0, (self.start.y + self.end.y) / 2.0)
+    }
+}
+
+impl From<Line<f32>> for Path {
+    fn from(line: Line<f32>) -> Self {
+        // left open so that strokes have caps at both ends
+        let mut builder = PathBuilder::new();
+
+        builder.open(line.start);
+        builder.line_to(line.end);
+
+        builder.build_open()
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl<T: Into<f64>> From<Line<T>> for vello::kurbo::Line {
+    fn from(line: Line<T>) -> Self {
+        let Line { start, end } = line;
+
+        vello::kurbo::Line::new((start.x.into(), start.y.into()), (end.x.into(), end.y.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::PathElement;
+    use crate::math::Polyline;
+
+    #[test]
+    fn lines_and_open_polylines_are_not_closed() {
+        let line = Line::new(Point2::new(0.0, 0.0), Point2::new(3.0, 4.0));
+
+        assert_eq!(line.length(), 5.0);
+        assert_eq!(
+            Path::from(line).iter().collect::<Vec<_>>(),
+            [PathElement::Open(line.start), PathElement::LineTo(line.end)],
+        );
+
+        let polyline = Polyline::new([line.start, line.end, Point2::new(3.0, 0.0)], false);
+
+        assert_ne!(Path::from(polyline.clone()).iter().last(), Some(PathElement::Close));
+        assert_eq!(
+            Path::from(Polyline { closed: true, ..polyline }).iter().last(),
+            Some(PathElement::Close),
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "renderer")]
+    fn shapes_of_f32_convert_to_kurbo() {
+        use vello::kurbo;
+
+        let line = kurbo::Line::from(Line::new(Point2::new(0.0f32, 0.5), Point2::new(3.0, 4.0)));
+
+        assert_eq!(line, kurbo::Line::new((0.0, 0.5), (3.0, 4.0)));
+        assert_eq!(
+            kurbo::BezPath::from(Polyline::new([Point2::new(1.0f32, 2.0)], true)),
+            kurbo::BezPath::from(Polyline::new([Point2::new(1.0f64, 2.0)], true)),
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::{Add, Div, Mul, MulAssign, Neg, Sub};
 
 use super::{Infinity, Mat2, Max, Min, NegOne, One, Trig, Vec2, Zero};
 
@@ -16,6 +17,28 @@ impl<T> Mat2<T> {
         Self::from_columns(Vec2::from_x(scale.x), Vec2::from_y(scale.y))
     }
 
+    /// Returns a matrix that rotates vectors by an angle in radians, clockwise
+    /// (`y`-down).
+    pub fn from_rotation(angle: T) -> Self
+    where
+        T: Trig + Neg<Output = T>,
+    {
+        let (sin, cos) = angle.sin_cos();
+
+        Self::from_columns(Vec2::new(cos, sin), Vec2::new(-sin, cos))
+    }
+
+    /// Returns a matrix that skews vectors by angles in radians.
+    ///
+    /// `skew.x` skews along the `x`-axis (like SVG `skewX`) and `skew.y` along
+    /// the `y`-axis.
+    pub fn from_skew(skew: Vec2<T>) -> Self
+    where
+        T: Trig + One,
+    {
+        Self::from_columns(Vec2::new(T::ONE, skew.y.tan()), Vec2::new(skew.x.tan(), T::ONE))
+    }
+
     /// Returns a matrix with columns set to `v`.
     pub const fn splat(v: Vec2<T>) -> Self
     where
@@ -57,6 +80,38 @@ impl<T> Mat2<T> {
     }
 }
 
+impl<T> Mat2<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Returns the determinant of this matrix.
+    pub fn determinant(self) -> T {
+        self.x.x * self.y.y - self.y.x * self.x.y
+    }
+
+    /// Returns the inverse of this matrix, or `None` if it isn't invertible.
+    pub fn inverse(self) -> Option<Self>
+    where
+        T: Zero + PartialEq + Div<Output = T> + Neg<Output = T>,
+    {
+        let determinant = self.determinant();
+
+        if determinant == T::ZERO {
+            return None;
+        }
+
+        Some(Self::from_columns(
+            Vec2::new(self.y.y / determinant, -self.x.y / determinant),
+            Vec2::new(-self.y.x / determinant, self.x.x / determinant),
+        ))
+    }
+
+    /// Returns the transpose of this matrix.
+    pub fn transpose(self) -> Self {
+        Self::from_columns(Vec2::new(self.x.x, self.y.x), Vec2::new(self.x.y, self.y.y))
+    }
+}
+
 impl<T, U> Mat2<(T, U)> {
     /// Unzips this matrix back into 2 matrices.
     pub fn unzip(self) -> (Mat2<T>, Mat2<U>) {
@@ -119,6 +174,38 @@ impl<T: Trig> Trig for Mat2<T> {
     }
 }
 
+impl<T> Mul<Vec2<T>> for Mat2<T>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Vec2<T>;
+
+    fn mul(self, rhs: Vec2<T>) -> Self::Output {
+        Vec2::new(self.x.x * rhs.x + self.y.x * rhs.y, self.x.y * rhs.x + self.y.y * rhs.y)
+    }
+}
+
+/// Composes 2 matrices, applying `rhs` first.
+impl<T> Mul for Mat2<T>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        rhs.map(|column| self * column)
+    }
+}
+
+impl<T> MulAssign for Mat2<T>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
 impl<T: fmt::Debug> fmt::Debug for Mat2<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         struct DebugVec2<'a, T>(&'a Vec2<T>);
@@ -143,4 +230,16 @@ mod tests {
 
         assert_eq!(&format!("{:?}", mat), "[[1, 2], [3, 4]]");
     }
+
+    #[test]
+    fn multiplication_and_inversion() {
+        let a = Mat2::from_columns(Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0));
+        let b = Mat2::from_scale(Vec2::new(2.0, 0.5));
+
+        assert_eq!(a * Vec2::new(1.0, 1.0), Vec2::new(4.0, 6.0));
+        assert_eq!(a * b, Mat2::from_columns(Vec2::new(2.0, 4.0), Vec2::new(1.5, 2.0)));
+        assert_eq!(a.determinant(), -2.0);
+        assert_eq!(a * a.inverse().unwrap(), Mat2::IDENTITY);
+        assert_eq!(Mat2::splat(Vec2::new(1.0, 1.0)).inverse(), None);
+    }
 }
@@ -1,12 +1,19 @@
 //! Simple math types.
 
 mod affine2;
+mod circle;
+mod ellipse;
+mod line;
 mod macros;
 mod mat2;
 mod num;
 mod point2;
+mod polygon;
+mod polyline;
 mod rect;
+mod rounded_rect;
 mod size2;
+mod star;
 mod vec2;
 
 pub use self::num::*;
@@ -54,3 +61,77 @@ pub struct Rect<T> {
     /// The width and height of the rectangle (`y`-down).
     pub size: Size2<T>,
 }
+
+/// A rectangle with rounded corners.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RoundedRect<T> {
+    pub rect: Rect<T>,
+    pub radii: CornerRadii<T>,
+}
+
+/// The radius of each corner of a [`RoundedRect`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CornerRadii<T> {
+    pub top_left: T,
+    pub top_right: T,
+    pub bottom_right: T,
+    pub bottom_left: T,
+}
+
+/// A circle defined by its center and radius.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Circle<T> {
+    pub center: Point2<T>,
+    pub radius: T,
+}
+
+/// An ellipse defined by its center, radii, and rotation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ellipse<T> {
+    pub center: Point2<T>,
+    /// The radii along the `x` and `y` axes of the ellipse.
+    pub radii: Vec2<T>,
+    /// The rotation of the axes of the ellipse in radians.
+    pub rotation: T,
+}
+
+/// A line segment between 2 points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Line<T> {
+    pub start: Point2<T>,
+    pub end: Point2<T>,
+}
+
+/// A regular polygon.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Polygon<T> {
+    pub center: Point2<T>,
+    /// The distance from the center to each vertex.
+    pub radius: T,
+    pub sides: usize,
+    /// The rotation of the polygon in radians. With no rotation, the first
+    /// vertex points up (`y`-down).
+    pub rotation: T,
+}
+
+/// A regular star.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Star<T> {
+    pub center: Point2<T>,
+    /// The distance from the center to the tip of each point.
+    pub outer_radius: T,
+    /// The distance from the center to the vertices between points.
+    pub inner_radius: T,
+    pub points: usize,
+    /// The rotation of the star in radians. With no rotation, the first point
+    /// points up (`y`-down).
+    pub rotation: T,
+}
+
+/// A sequence of connected line segments.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Polyline<T> {
+    pub points: Vec<Point2<T>>,
+    /// Whether the last point connects back to the first.
+    pub closed: bool,
+}
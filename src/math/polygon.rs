@@ -0,0 +1,116 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use super::{Point2, Polygon};
+use crate::element::Path;
+
+impl<T> Polygon<T> {
+    /// Returns a regular polygon from its center, radius, amount of sides, and
+    /// rotation in radians.
+    pub const fn new(center: Point2<T>, radius: T, sides: usize, rotation: T) -> Self {
+        Self { center, radius, sides, rotation }
+    }
+}
+
+impl Polygon<f32> {
+    /// Returns an iterator of the vertices of this polygon, clockwise
+    /// (`y`-down).
+    pub fn vertices(self) -> impl Iterator<Item = Point2<f32>> {
+        let Self { center, radius, sides, rotation } = self;
+
+        (0..sides).map(move |i| vertex(center, radius, rotation + TAU * i as f32 / sides as f32))
+    }
+}
+
+impl From<Polygon<f32>> for Path {
+    fn from(polygon: Polygon<f32>) -> Self {
+        polygon_path(polygon.vertices())
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl<T: Into<f64>> From<Polygon<T>> for vello::kurbo::BezPath {
+    fn from(polygon: Polygon<T>) -> Self {
+        let Polygon { center, radius, sides, rotation } = polygon;
+
+        kurbo_polygon(center.map(Into::into), &[radius.into()], sides, rotation.into())
+    }
+}
+
+/// Returns the vertex of a regular shape at an angle, where `0` points up.
+pub(super) fn vertex(center: Point2<f32>, radius: f32, angle: f32) -> Point2<f32> {
+    let (sin, cos) = (angle - FRAC_PI_2).sin_cos();
+
+    Point2::new(center.x + radius * cos, center.y + radius * sin)
+}
+
+/// Returns a closed path through vertices.
+pub(super) fn polygon_path(vertices: impl IntoIterator<Item = Point2<f32>>) -> Path {
+    Path::from_fn(|builder| {
+        for (i, vertex) in vertices.into_iter().enumerate() {
+            if i == 0 {
+                builder.open(vertex);
+            } else {
+                builder.line_to(vertex);
+            }
+        }
+    })
+}
+
+/// Returns a closed path through evenly spaced vertices around a center,
+/// cycling through `radii`.
+#[cfg(feature = "renderer")]
+pub(super) fn kurbo_polygon(
+    center: Point2<f64>,
+    radii: &[f64],
+    vertices: usize,
+    rotation: f64,
+) -> vello::kurbo::BezPath {
+    use std::f64::consts::{FRAC_PI_2, TAU};
+
+    use vello::kurbo;
+
+    let mut path = kurbo::BezPath::new();
+
+    for i in 0..vertices {
+        let angle = rotation + TAU * i as f64 / vertices as f64 - FRAC_PI_2;
+        let radius = radii[i % radii.len()];
+        let point =
+            kurbo::Point::new(center.x + radius * angle.cos(), center.y + radius * angle.sin());
+
+        if i == 0 {
+            path.move_to(point);
+        } else {
+            path.line_to(point);
+        }
+    }
+
+    if vertices > 0 {
+        path.close_path();
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Star;
+
+    #[test]
+    fn the_first_vertex_points_up() {
+        let polygon = Polygon::new(Point2::new(0.0, 0.0), 10.0, 6, 0.0);
+        let vertices: Vec<_> = polygon.vertices().collect();
+
+        assert_eq!(vertices.len(), 6);
+        assert!(vertices[0].x.abs() < 1e-5 && (vertices[0].y + 10.0).abs() < 1e-5);
+        // clockwise (`y`-down)
+        assert!(vertices[1].x > 0.0);
+
+        let star = Star::new(Point2::new(0.0, 0.0), 10.0, 4.0, 5, 0.0);
+        let vertices: Vec<_> = star.vertices().collect();
+
+        assert_eq!(vertices.len(), 10);
+        assert!((vertices[1].x.hypot(vertices[1].y) - 4.0).abs() < 1e-5);
+        assert_eq!(Path::from(star).len(), 11);
+    }
+}
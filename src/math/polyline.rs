@@ -0,0 +1,56 @@
+use super::{Point2, Polyline};
+use crate::element::{Path, PathBuilder};
+
+impl<T> Polyline<T> {
+    /// Returns a polyline through points.
+    pub fn new(points: impl IntoIterator<Item = Point2<T>>, closed: bool) -> Self {
+        Self { points: points.into_iter().collect(), closed }
+    }
+}
+
+impl From<Polyline<f32>> for Path {
+    fn from(polyline: Polyline<f32>) -> Self {
+        let mut builder = PathBuilder::new();
+
+        for (i, &point) in polyline.points.iter().enumerate() {
+            if i == 0 {
+                builder.open(point);
+            } else {
+                builder.line_to(point);
+            }
+        }
+
+        if polyline.closed {
+            builder.build()
+        } else {
+            builder.build_open()
+        }
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl<T: Into<f64>> From<Polyline<T>> for vello::kurbo::BezPath {
+    fn from(polyline: Polyline<T>) -> Self {
+        use vello::kurbo;
+
+        let Polyline { points, closed } = polyline;
+        let closed = closed && !points.is_empty();
+        let mut path = kurbo::BezPath::new();
+
+        for (i, Point2 { x, y }) in points.into_iter().enumerate() {
+            let point = (x.into(), y.into());
+
+            if i == 0 {
+                path.move_to(point);
+            } else {
+                path.line_to(point);
+            }
+        }
+
+        if closed {
+            path.close_path();
+        }
+
+        path
+    }
+}
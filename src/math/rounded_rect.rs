@@ -0,0 +1,174 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use super::{CornerRadii, Point2, Rect, RoundedRect, Vec2};
+use crate::element::{Path, QUARTER_TURNS};
+
+impl<T> RoundedRect<T> {
+    /// Returns a rounded rectangle from a rectangle and its corner radii.
+    pub const fn new(rect: Rect<T>, radii: CornerRadii<T>) -> Self {
+        Self { rect, radii }
+    }
+}
+
+impl RoundedRect<f32> {
+    /// Returns the corner radii, scaled down so that adjacent corners don't
+    /// overlap (like CSS `border-radius`).
+    pub fn clamped_radii(self) -> CornerRadii<f32> {
+        let CornerRadii { top_left, top_right, bottom_right, bottom_left } =
+            self.radii.map(|radius| radius.max(0.0));
+        let (w, h) = (self.rect.size.w.abs(), self.rect.size.h.abs());
+
+        let scale = [
+            w / (top_left + top_right),
+            h / (top_right + bottom_right),
+            w / (bottom_right + bottom_left),
+            h / (bottom_left + top_left),
+        ]
+        .into_iter()
+        .filter(|scale| !scale.is_nan())
+        .fold(1.0, f32::min);
+
+        CornerRadii::new(top_left, top_right, bottom_right, bottom_left)
+            .map(|radius| radius * scale)
+    }
+
+    /// Returns a path of this rounded rectangle, with corners approximated
+    /// with cubic segments that deviate from them by at most `tolerance`.
+    ///
+    /// Converting into a [`Path`] uses a segment per corner instead, which is
+    /// accurate to ~0.03% of its radius.
+    pub fn to_path(self, tolerance: f32) -> Path {
+        let radii = self.clamped_radii();
+        let rect = self.rect;
+        let (left, top, right, bottom) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+
+        // clockwise from the end of the upper-left corner
+        let corners = [
+            (Point2::new(right, top), radii.top_right, Vec2::new(-1.0, 1.0), -FRAC_PI_2),
+            (Point2::new(right, bottom), radii.bottom_right, Vec2::new(-1.0, -1.0), 0.0),
+            (Point2::new(left, bottom), radii.bottom_left, Vec2::new(1.0, -1.0), FRAC_PI_2),
+            (Point2::new(left, top), radii.top_left, Vec2::new(1.0, 1.0), PI),
+        ];
+
+        Path::from_fn(|builder| {
+            let start = Point2::new(left + radii.top_left, top);
+            // zero-length lines, including one back to the start, are skipped
+            let mut current = Some(start);
+
+            builder.open(start);
+
+            for (corner, radius, inwards, start_angle) in corners {
+                if radius > 0.0 {
+                    let center = corner + Vec2::new(inwards.x * radius, inwards.y * radius);
+
+                    builder.arc_to(
+                        center,
+                        Vec2::splat(radius),
+                        start_angle,
+                        FRAC_PI_2,
+                        0.0,
+                        tolerance,
+                    );
+                    current = None;
+                } else if Some(corner) != current && corner != start {
+                    builder.line_to(corner);
+                    current = Some(corner);
+                }
+            }
+        })
+    }
+}
+
+impl<T> CornerRadii<T> {
+    /// Returns corner radii, clockwise from the upper-left corner.
+    pub const fn new(top_left: T, top_right: T, bottom_right: T, bottom_left: T) -> Self {
+        Self { top_left, top_right, bottom_right, bottom_left }
+    }
+
+    /// Returns corner radii that are all `v`.
+    pub const fn splat(v: T) -> Self
+    where
+        T: Copy,
+    {
+        Self::new(v, v, v, v)
+    }
+
+    /// Maps each radius.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> CornerRadii<U> {
+        let Self { top_left, top_right, bottom_right, bottom_left } = self;
+
+        CornerRadii::new(f(top_left), f(top_right), f(bottom_right), f(bottom_left))
+    }
+}
+
+impl<T: Copy> From<T> for CornerRadii<T> {
+    fn from(v: T) -> Self {
+        Self::splat(v)
+    }
+}
+
+impl From<Rect<f32>> for RoundedRect<f32> {
+    fn from(rect: Rect<f32>) -> Self {
+        Self::new(rect, CornerRadii::splat(0.0))
+    }
+}
+
+impl From<RoundedRect<f32>> for Path {
+    fn from(rounded_rect: RoundedRect<f32>) -> Self {
+        rounded_rect.to_path(QUARTER_TURNS)
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl<T: Into<f64>> From<RoundedRect<T>> for vello::kurbo::RoundedRect {
+    fn from(rounded_rect: RoundedRect<T>) -> Self {
+        use vello::kurbo;
+
+        let CornerRadii { top_left, top_right, bottom_right, bottom_left } =
+            rounded_rect.radii.map(Into::into);
+
+        kurbo::RoundedRect::from_rect(
+            rounded_rect.rect.map(Into::into).into(),
+            kurbo::RoundedRectRadii::new(top_left, top_right, bottom_right, bottom_left),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::PathElement;
+    use crate::math::Size2;
+
+    #[test]
+    fn radii_are_clamped_to_the_size() {
+        let rect = Rect::from_size(Size2::new(60.0, 100.0));
+        let rounded_rect = RoundedRect::new(rect, CornerRadii::new(40.0, 40.0, 0.0, -10.0));
+
+        assert_eq!(rounded_rect.clamped_radii(), CornerRadii::new(30.0, 30.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn square_corners_are_lines() {
+        let rect = Rect::from_size(Size2::splat(10.0));
+        let path = Path::from(RoundedRect::new(rect, CornerRadii::new(0.0, 2.0, 0.0, 0.0)));
+
+        assert_eq!(
+            path.iter().filter(|element| matches!(element, PathElement::CubicTo { .. })).count(),
+            1,
+        );
+        assert!(Path::from(RoundedRect::from(rect)).iter().eq(Path::from(rect).iter()));
+    }
+
+    #[test]
+    fn corners_are_split_within_the_tolerance() {
+        let rect = Rect::from_size(Size2::splat(100.0));
+        let rounded_rect = RoundedRect::new(rect, CornerRadii::splat(40.0));
+        let cubics = |path: Path| {
+            path.iter().filter(|element| matches!(element, PathElement::CubicTo { .. })).count()
+        };
+
+        assert_eq!(cubics(rounded_rect.into()), 4);
+        assert!(cubics(rounded_rect.to_path(0.001)) > 4);
+    }
+}
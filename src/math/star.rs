@@ -0,0 +1,54 @@
+use std::f32::consts::TAU;
+
+use super::polygon::{polygon_path, vertex};
+use super::{Point2, Star};
+use crate::element::Path;
+
+impl<T> Star<T> {
+    /// Returns a regular star from its center, radii, amount of points, and
+    /// rotation in radians.
+    pub const fn new(
+        center: Point2<T>,
+        outer_radius: T,
+        inner_radius: T,
+        points: usize,
+        rotation: T,
+    ) -> Self {
+        Self { center, outer_radius, inner_radius, points, rotation }
+    }
+}
+
+impl Star<f32> {
+    /// Returns an iterator of the vertices of this star, clockwise (`y`-down),
+    /// alternating between the tips of points and the vertices between them.
+    pub fn vertices(self) -> impl Iterator<Item = Point2<f32>> {
+        let Self { center, outer_radius, inner_radius, points, rotation } = self;
+        let vertices = 2 * points;
+
+        (0..vertices).map(move |i| {
+            let radius = if i % 2 == 0 { outer_radius } else { inner_radius };
+
+            vertex(center, radius, rotation + TAU * i as f32 / vertices as f32)
+        })
+    }
+}
+
+impl From<Star<f32>> for Path {
+    fn from(star: Star<f32>) -> Self {
+        polygon_path(star.vertices())
+    }
+}
+
+#[cfg(feature = "renderer")]
+impl<T: Into<f64>> From<Star<T>> for vello::kurbo::BezPath {
+    fn from(star: Star<T>) -> Self {
+        let Star { center, outer_radius, inner_radius, points, rotation } = star;
+
+        super::polygon::kurbo_polygon(
+            center.map(Into::into),
+            &[outer_radius.into(), inner_radius.into()],
+            2 * points,
+            rotation.into(),
+        )
+    }
+}
@@ -94,7 +94,7 @@ impl Vec2<bool> {
     /// Selects components from `if_true` if the component in `self` is `true`.
     pub fn select<T>(self, if_true: Vec2<T>, if_false: Vec2<T>) -> Vec2<T> {
         self.zip(if_true.zip(if_false))
-            .map(|(cond, (if_true, if_false))| cond.then_some(if_true).unwrap_or(if_false))
+            .map(|(cond, (if_true, if_false))| if cond { if_true } else { if_false })
     }
 }
 
@@ -1,11 +1,53 @@
+use std::borrow::Cow;
+use std::sync::mpsc;
+
 use thiserror::Error;
-use vello::wgpu::{Device, Queue, SurfaceTexture, Texture, TextureFormat, TextureViewDescriptor};
+use vello::glyph::skrifa::prelude::NormalizedCoord;
+use vello::wgpu::{
+    BufferAsyncError,
+    BufferDescriptor,
+    BufferUsages,
+    Device,
+    Extent3d,
+    ImageCopyBuffer,
+    ImageDataLayout,
+    Maintain,
+    MapMode,
+    Queue,
+    SurfaceTexture,
+    Texture,
+    TextureDescriptor,
+    TextureDimension,
+    TextureFormat,
+    TextureUsages,
+    TextureViewDescriptor,
+    COPY_BYTES_PER_ROW_ALIGNMENT,
+};
 use vello::{kurbo, peniko};
 pub use vello::{AaConfig, AaSupport};
 
-use crate::element::{Color, FillStyle, Layer, TextContext, TextLayout};
-use crate::math::{Affine2, Max, Rect, Size2};
-use crate::{Command, Scene};
+use crate::element::{
+    Brush,
+    Color,
+    FillStyle,
+    Glyph,
+    GlyphRun,
+    Image,
+    ImageBrush,
+    ImageQuality,
+    Layer,
+    Path,
+    Source,
+    StrokeStyle,
+    TextContext,
+    TextLayout,
+    TextStyle,
+};
+#[cfg(feature = "png")]
+use crate::element::{PngDescriptor, PngError};
+use crate::math::{Affine2, Mat2, Max, Rect, Size2, Vec2};
+use crate::util::max_scale;
+use crate::{Backend, Scene, SceneError};
 
 /// A renderer for a [`Scene`].
 pub struct Renderer {
@@ -24,8 +66,8 @@ pub struct RendererDescriptor {
     pub antialiasing_support: AaSupport,
 }
 
-/// Parameters for calling [`Renderer::render_to_texture`] and
-/// [`Renderer::render_to_surface`].
+/// Parameters for calling [`Renderer::render_to_texture`],
+/// [`Renderer::render_to_surface`] and [`Renderer::render_to_image`].
 #[derive(Clone, Copy)]
 pub struct RenderDescriptor {
     /// The method of anti-aliasing to use.
@@ -55,69 +97,32 @@ impl Renderer {
     }
 
     /// Encodes scene data.
+    ///
+    /// Returns an error if the scene has unbalanced layers (see
+    /// [`Scene::finish`]).
     pub fn prepare(
         &mut self,
         text_cx: &mut TextContext,
         scene: &Scene,
         global_transform: Affine2<f32>,
-    ) {
+    ) -> Result<(), RendererError> {
+        scene.finish()?;
+
         let needs_final_transform = global_transform != Affine2::IDENTITY;
 
         self.output.reset();
         self.scratch.reset();
 
-        {
-            let output = if !needs_final_transform { &mut self.output } else { &mut self.scratch };
-
-            for command in scene {
-                match command {
-                    Command::Fill { path, brush, style } => {
-                        let FillStyle { rule } = *style;
-
-                        let style: peniko::Fill = rule.into();
-                        let brush: peniko::Brush = brush.clone().into();
-
-                        output.fill(style, kurbo::Affine::IDENTITY, &brush, None, path);
-                    },
-                    Command::Stroke { path, brush, style } => {
-                        let stroke: kurbo::Stroke = (*style).into();
-                        let brush: peniko::Brush = brush.clone().into();
-
-                        output.stroke(&stroke, kurbo::Affine::IDENTITY, &brush, None, path);
-                    },
-                    Command::DrawText { source, bounds, style } => {
-                        // TODO: cache layouts
-                        let mut layout = TextLayout::new();
-
-                        // TODO: respect vertical bounds
-                        layout.build(text_cx, source, style.clone());
-                        layout.break_lines(bounds.size.w, style.alignment);
-                        layout.render(bounds.origin, output);
-                    },
-                    Command::DrawTextLayout { layout, origin } => {
-                        layout.render(*origin, output);
-                    },
-                    Command::PushLayer(layer) => {
-                        let Layer { transform, blend_mode, clip, alpha } = layer;
-
-                        let transform: kurbo::Affine = (*transform).into();
-
-                        if let Some(clip) = clip {
-                            output.push_layer(*blend_mode, *alpha, transform, clip);
-                        } else {
-                            let clip: kurbo::Rect = Rect::from_size(Size2::MAX).into();
-
-                            output.push_layer(*blend_mode, *alpha, transform, &clip);
-                        }
-                    },
-                    Command::PopLayer => output.pop_layer(),
-                }
-            }
-        }
+        let output = if !needs_final_transform { &mut self.output } else { &mut self.scratch };
+
+        // already checked before resetting the output
+        scene.replay_unchecked(&mut VelloBackend { output, text_cx, transforms: Vec::new() });
 
         if needs_final_transform {
             self.output.append(&self.scratch, Some(global_transform.into()));
         }
+
+        Ok(())
     }
 
     /// Renders prepared data to a texture.
@@ -148,6 +153,114 @@ impl Renderer {
         Ok(())
     }
 
+    /// Renders prepared data to a new [`Image`].
+    ///
+    /// The returned image has straight (not premultiplied) alpha.
+    pub fn render_to_image(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        size: Size2<u32>,
+        desc: &RenderDescriptor,
+    ) -> Result<Image, RendererError> {
+        let Size2 { w: width, h: height } = size;
+
+        if width == 0 || height == 0 {
+            return Ok(Image::new(Vec::new(), width, height).expect("image is empty"));
+        }
+
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("keibrush::Renderer::render_to_image"),
+            size: Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::STORAGE_BINDING | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        self.render_to_texture(device, queue, &texture, desc)?;
+
+        // rows copied out of a texture must be padded to a multiple of
+        // `COPY_BYTES_PER_ROW_ALIGNMENT`
+        let row_len = width * 4;
+        let padded_row_len = row_len.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("keibrush::Renderer::render_to_image"),
+            size: padded_row_len as u64 * height as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_len),
+                    rows_per_image: None,
+                },
+            },
+            texture.size(),
+        );
+
+        let submission = queue.submit([encoder.finish()]);
+        let slice = buffer.slice(..);
+        let (sender, receiver) = mpsc::channel();
+
+        slice.map_async(MapMode::Read, move |result| {
+            _ = sender.send(result);
+        });
+        device.poll(Maintain::wait_for(submission));
+        receiver.recv().unwrap_or(Err(BufferAsyncError))?;
+
+        let mut data = Vec::with_capacity(row_len as usize * height as usize);
+
+        for row in slice.get_mapped_range().chunks_exact(padded_row_len as _) {
+            data.extend_from_slice(&row[..row_len as _]);
+        }
+
+        buffer.unmap();
+
+        Ok(Image::new(data, width, height).expect("data is the size of the image"))
+    }
+
+    /// Renders prepared data and encodes it as PNG.
+    #[cfg(feature = "png")]
+    pub fn render_to_png(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        size: Size2<u32>,
+        desc: &RenderDescriptor,
+        png_desc: &PngDescriptor,
+    ) -> Result<Vec<u8>, RendererError> {
+        let image = self.render_to_image(device, queue, size, desc)?;
+
+        Ok(image.encode_png(png_desc)?)
+    }
+
+    /// Renders prepared data and saves it as a PNG file.
+    #[cfg(feature = "png")]
+    pub fn render_to_png_file(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        size: Size2<u32>,
+        desc: &RenderDescriptor,
+        png_desc: &PngDescriptor,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), RendererError> {
+        let image = self.render_to_image(device, queue, size, desc)?;
+
+        Ok(image.save_png(path, png_desc)?)
+    }
+
     /// Renders prepared data to a surface.
     pub fn render_to_surface(
         &mut self,
@@ -175,12 +288,156 @@ impl Renderer {
     }
 }
 
+/// Encodes a [`Scene`] into a [`vello::Scene`].
+struct VelloBackend<'a> {
+    output: &'a mut vello::Scene,
+    text_cx: &'a mut TextContext,
+    /// The transform of each open layer, applied to everything within it.
+    transforms: Vec<kurbo::Affine>,
+}
+
+impl VelloBackend<'_> {
+    fn transform(&self) -> kurbo::Affine {
+        self.transforms.last().copied().unwrap_or(kurbo::Affine::IDENTITY)
+    }
+
+    /// Extends the image of an image brush over the bounds it paints, as vello
+    /// draws nothing past the edges of images.
+    fn extend_image<'b>(&self, brush: &'b Brush, bounds: Rect<f32>) -> Cow<'b, Brush> {
+        let Brush::Image(image_brush) = brush else {
+            return Cow::Borrowed(brush);
+        };
+        // vello always samples bilinearly, so nearest-neighbor sampling is
+        // approximated by scaling the image up until each pixel covers a pixel of
+        // the target
+        let scale = match image_brush.quality {
+            ImageQuality::Low => {
+                let transform = self.transform() * kurbo::Affine::from(image_brush.transform);
+                let [a, b, c, d, ..] = transform.as_coeffs().map(|v| v as f32);
+
+                max_scale(Mat2::from_columns(Vec2::new(a, b), Vec2::new(c, d))).ceil() as u32
+            },
+            ImageQuality::Medium | ImageQuality::High => 1,
+        };
+
+        match image_brush.extend_over(bounds, scale) {
+            Some(image_brush) => Cow::Owned(Brush::Image(image_brush)),
+            None => Cow::Borrowed(brush),
+        }
+    }
+}
+
+impl Backend for VelloBackend<'_> {
+    fn fill(&mut self, path: &Path, brush: &Brush, style: &FillStyle) {
+        let FillStyle { rule } = *style;
+
+        let style: peniko::Fill = rule.into();
+        let brush = self.extend_image(brush, path.bounds());
+        let brush_transform = brush_transform(&brush);
+        let brush: peniko::Brush = brush.into_owned().into();
+
+        self.output.fill(style, self.transform(), &brush, brush_transform, path);
+    }
+
+    fn stroke(&mut self, path: &Path, brush: &Brush, style: &StrokeStyle) {
+        let stroke: kurbo::Stroke = style.into();
+        let brush =
+            self.extend_image(brush, path.bounds().with_insets(Vec2::splat(-style.reach())));
+        let brush_transform = brush_transform(&brush);
+        let brush: peniko::Brush = brush.into_owned().into();
+
+        self.output.stroke(&stroke, self.transform(), &brush, brush_transform, path);
+    }
+
+    fn draw_text(&mut self, source: &Source, bounds: Rect<f32>, style: &TextStyle) {
+        // TODO: cache layouts
+        let mut layout = TextLayout::new();
+
+        // TODO: respect vertical bounds
+        layout.build(self.text_cx, source, style.clone());
+        layout.break_lines(bounds.size.w, style.alignment);
+
+        self.draw_text_layout(&layout, bounds.origin);
+    }
+
+    fn draw_image(&mut self, image: &Image, bounds: Rect<f32>, _quality: ImageQuality) {
+        // vello always samples images bilinearly
+        let image: peniko::Image = image.into();
+        let scale =
+            Vec2::new(bounds.size.w / image.width as f32, bounds.size.h / image.height as f32);
+        let image_transform = Affine2::new(Mat2::from_scale(scale), bounds.origin.to_vec());
+
+        self.output.draw_image(&image, self.transform() * kurbo::Affine::from(image_transform));
+    }
+
+    fn draw_glyph_run(&mut self, glyph_run: &GlyphRun) {
+        let GlyphRun { font, font_size, normalized_coords, glyphs, brush } = glyph_run;
+
+        let coords: Vec<_> =
+            normalized_coords.iter().copied().map(NormalizedCoord::from_bits).collect();
+        let brush: peniko::Brush = brush.clone().into();
+        let transform = self.transform();
+
+        self.output
+            .draw_glyphs(font)
+            .brush(&brush)
+            .transform(transform)
+            .font_size(*font_size)
+            .normalized_coords(&coords)
+            .draw(
+                peniko::Fill::NonZero,
+                glyphs.iter().map(|&Glyph { id, position }| vello::glyph::Glyph {
+                    id,
+                    x: position.x,
+                    y: position.y,
+                }),
+            );
+    }
+
+    fn push_layer(&mut self, layer: &Layer) {
+        let Layer { transform: layer_transform, blend_mode, clip, alpha } = layer;
+
+        let transform = self.transform() * kurbo::Affine::from(*layer_transform);
+
+        if let Some(clip) = clip {
+            self.output.push_layer(*blend_mode, *alpha, transform, clip);
+        } else {
+            let clip: kurbo::Rect = Rect::from_size(Size2::MAX).into();
+
+            self.output.push_layer(*blend_mode, *alpha, kurbo::Affine::IDENTITY, &clip);
+        }
+
+        self.transforms.push(transform);
+    }
+
+    fn pop_layer(&mut self) {
+        self.output.pop_layer();
+        self.transforms.pop();
+    }
+}
+
+/// Returns the transform to apply to the brush of a fill or stroke.
+fn brush_transform(brush: &Brush) -> Option<kurbo::Affine> {
+    match brush {
+        Brush::Image(ImageBrush { transform, .. }) => Some((*transform).into()),
+        _ => None,
+    }
+}
+
 /// Error when creating a [`Renderer`] and when calling
-/// [`Renderer::render_to_texture`] or [`Renderer::render_to_surface`].
+/// [`Renderer::prepare`], [`Renderer::render_to_texture`],
+/// [`Renderer::render_to_surface`] or [`Renderer::render_to_image`].
 #[derive(Debug, Error)]
 pub enum RendererError {
     #[error(transparent)]
     Inner(#[from] vello::Error),
+    #[error(transparent)]
+    Scene(#[from] SceneError),
+    #[error(transparent)]
+    Readback(#[from] BufferAsyncError),
+    #[cfg(feature = "png")]
+    #[error(transparent)]
+    Png(#[from] PngError),
 }
 
 impl Default for RendererDescriptor {
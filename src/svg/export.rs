@@ -0,0 +1,578 @@
+use std::fmt::{self, Write};
+
+use crate::element::{
+    Brush,
+    Cap,
+    Color,
+    ColorStop,
+    Extend,
+    FillRule,
+    FillStyle,
+    Font,
+    FontFamily,
+    FontStyle,
+    Image,
+    ImageBrush,
+    ImageQuality,
+    Join,
+    Layer,
+    LinearGradient,
+    Mix,
+    Path,
+    RadialGradient,
+    Source,
+    Span,
+    StrokeStyle,
+    SweepGradient,
+    TextAlignment,
+    TextStyle,
+};
+use crate::math::{Affine2, Mat2, Rect, Size2};
+use crate::util::base64;
+use crate::{Backend, Scene};
+
+impl Scene {
+    /// Writes this scene as an SVG document.
+    ///
+    /// Layers that are still open are closed at the end of the document.
+    ///
+    /// Not everything in a scene can be expressed in SVG, so some things are
+    /// approximated:
+    ///
+    /// - Sweep gradients are painted with the color of their first stop.
+    /// - Image brushes are always repeated.
+    /// - Only the [`Mix`] of a layer's blend mode is written.
+    /// - Only the start cap of a stroke is written.
+    pub fn to_svg(&self, size: Size2<f32>) -> String {
+        let mut writer = SvgWriter::new();
+
+        self.replay_unchecked(&mut writer);
+
+        writer.finish(size)
+    }
+}
+
+/// Writes a [`Scene`] as SVG.
+struct SvgWriter {
+    /// Gradients, patterns and clip paths referenced by the body.
+    defs: String,
+    body: String,
+    /// The id of the next definition.
+    next_id: usize,
+    /// The amount of open layers.
+    depth: usize,
+}
+
+impl SvgWriter {
+    fn new() -> Self {
+        Self { defs: String::new(), body: String::new(), next_id: 0, depth: 0 }
+    }
+
+    fn finish(mut self, size: Size2<f32>) -> String {
+        while self.depth > 0 {
+            self.pop_layer();
+        }
+
+        let Size2 { w, h } = size;
+        let mut out = String::new();
+
+        _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        );
+
+        if !self.defs.is_empty() {
+            out.push_str("  <defs>\n");
+            out.push_str(&self.defs);
+            out.push_str("  </defs>\n");
+        }
+
+        out.push_str(&self.body);
+        out.push_str("</svg>\n");
+
+        out
+    }
+
+    /// Returns a new unique id for a definition.
+    fn id(&mut self, prefix: &str) -> String {
+        let id = format!("{prefix}{}", self.next_id);
+
+        self.next_id += 1;
+
+        id
+    }
+
+    /// Starts a new line in the body.
+    fn indent(&mut self) {
+        for _ in 0..=self.depth {
+            self.body.push_str("  ");
+        }
+    }
+
+    /// Writes the attributes for painting with a brush, defining it if needed.
+    ///
+    /// `attr` is either `fill` or `stroke`.
+    fn paint(&mut self, attr: &str, brush: &Brush) {
+        match brush {
+            Brush::Solid(color) => {
+                write_color(&mut self.body, attr, &format!("{attr}-opacity"), *color);
+            },
+            Brush::LinearGradient(LinearGradient { start, end, extend, stops }) => {
+                let id = self.id("paint");
+
+                _ = writeln!(
+                    self.defs,
+                    r#"    <linearGradient id="{id}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}" spreadMethod="{}">"#,
+                    start.x,
+                    start.y,
+                    end.x,
+                    end.y,
+                    spread_method(*extend),
+                );
+                write_stops(&mut self.defs, stops);
+                self.defs.push_str("    </linearGradient>\n");
+
+                _ = write!(self.body, r#" {attr}="url(#{id})""#);
+            },
+            Brush::RadialGradient(RadialGradient {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+                extend,
+                stops,
+            }) => {
+                let id = self.id("paint");
+
+                // the focal circle is the start circle
+                _ = writeln!(
+                    self.defs,
+                    r#"    <radialGradient id="{id}" gradientUnits="userSpaceOnUse" cx="{}" cy="{}" r="{end_radius}" fx="{}" fy="{}" fr="{start_radius}" spreadMethod="{}">"#,
+                    end_center.x,
+                    end_center.y,
+                    start_center.x,
+                    start_center.y,
+                    spread_method(*extend),
+                );
+                write_stops(&mut self.defs, stops);
+                self.defs.push_str("    </radialGradient>\n");
+
+                _ = write!(self.body, r#" {attr}="url(#{id})""#);
+            },
+            Brush::SweepGradient(SweepGradient { stops, .. }) => {
+                // SVG has no sweep gradients
+                let color = stops.first().map_or(Color::TRANSPARENT, |stop| stop.color);
+
+                write_color(&mut self.body, attr, &format!("{attr}-opacity"), color);
+            },
+            Brush::Image(ImageBrush { image, transform, quality, .. }) => {
+                let Some(href) = data_url(image) else {
+                    _ = write!(self.body, r#" {attr}="none""#);
+
+                    return;
+                };
+                let id = self.id("paint");
+                let (w, h) = (image.width(), image.height());
+
+                _ = write!(
+                    self.defs,
+                    r#"    <pattern id="{id}" patternUnits="userSpaceOnUse" width="{w}" height="{h}""#,
+                );
+                write_transform(&mut self.defs, "patternTransform", *transform);
+                _ = write!(self.defs, ">\n      <image width=\"{w}\" height=\"{h}\"");
+                write_image_rendering(&mut self.defs, *quality);
+                _ = writeln!(self.defs, r#" xlink:href="{href}"/>"#);
+                self.defs.push_str("    </pattern>\n");
+
+                _ = write!(self.body, r#" {attr}="url(#{id})""#);
+            },
+        }
+    }
+}
+
+impl Backend for SvgWriter {
+    fn fill(&mut self, path: &Path, brush: &Brush, style: &FillStyle) {
+        let FillStyle { rule } = *style;
+
+        self.indent();
+        self.body.push_str("<path");
+        write_path_data(&mut self.body, path);
+        self.paint("fill", brush);
+
+        if rule == FillRule::EvenOdd {
+            self.body.push_str(r#" fill-rule="evenodd""#);
+        }
+
+        self.body.push_str("/>\n");
+    }
+
+    fn stroke(&mut self, path: &Path, brush: &Brush, style: &StrokeStyle) {
+        let StrokeStyle { width, join, start, end: _, miter_limit, dash_offset, .. } = *style;
+
+        self.indent();
+        self.body.push_str("<path");
+        write_path_data(&mut self.body, path);
+        self.body.push_str(r#" fill="none""#);
+        self.paint("stroke", brush);
+
+        let linecap = match start {
+            Cap::Butt => "butt",
+            Cap::Square => "square",
+            Cap::Round => "round",
+        };
+        let linejoin = match join {
+            Join::Bevel => "bevel",
+            Join::Miter => "miter",
+            Join::Round => "round",
+        };
+
+        _ = write!(
+            self.body,
+            r#" stroke-width="{width}" stroke-linecap="{linecap}" stroke-linejoin="{linejoin}""#,
+        );
+
+        if join == Join::Miter {
+            _ = write!(self.body, r#" stroke-miterlimit="{miter_limit}""#);
+        }
+
+        if let Some(dashes) = style.dashes() {
+            self.body.push_str(r#" stroke-dasharray=""#);
+
+            for (i, length) in dashes.enumerate() {
+                _ = write!(self.body, "{}{length}", if i > 0 { " " } else { "" });
+            }
+
+            _ = write!(self.body, r#"" stroke-dashoffset="{dash_offset}""#);
+        }
+
+        self.body.push_str("/>\n");
+    }
+
+    fn draw_text(&mut self, source: &Source, bounds: Rect<f32>, style: &TextStyle) {
+        let TextStyle { font, color, size, alignment } = style;
+        let Font { family, fallback, style: font_style, weight } = font;
+
+        let (x, anchor) = match alignment {
+            TextAlignment::Start => (bounds.left(), None),
+            TextAlignment::Middle => (bounds.left() + bounds.size.w / 2.0, Some("middle")),
+            TextAlignment::End => (bounds.right(), Some("end")),
+        };
+
+        self.indent();
+        _ = write!(self.body, r#"<text x="{x}" y="{}""#, bounds.top());
+        write_font_family(&mut self.body, family, fallback);
+        _ = write!(self.body, r#" font-size="{size}" font-weight="{}""#, weight.get());
+
+        if *font_style == FontStyle::Italic {
+            self.body.push_str(r#" font-style="italic""#);
+        }
+
+        write_color(&mut self.body, "fill", "fill-opacity", *color);
+
+        if let Some(anchor) = anchor {
+            _ = write!(self.body, r#" text-anchor="{anchor}""#);
+        }
+
+        // the bounds describe the top of the text, not the baseline
+        self.body.push_str(r#" dominant-baseline="text-before-edge" xml:space="preserve">"#);
+
+        match source {
+            Source::Plain(text) => _ = write!(self.body, "{}", Escaped(text)),
+            Source::Rich(spans) => {
+                for Span { source, font_family, font_style, font_weight, color, size } in spans {
+                    self.body.push_str("<tspan");
+
+                    if let Some(font_family) = font_family {
+                        write_font_family(&mut self.body, font_family, fallback);
+                    }
+                    if let Some(font_style) = font_style {
+                        let font_style = match font_style {
+                            FontStyle::Normal => "normal",
+                            FontStyle::Italic => "italic",
+                        };
+
+                        _ = write!(self.body, r#" font-style="{font_style}""#);
+                    }
+                    if let Some(font_weight) = font_weight {
+                        _ = write!(self.body, r#" font-weight="{}""#, font_weight.get());
+                    }
+                    if let Some(color) = color {
+                        write_color(&mut self.body, "fill", "fill-opacity", *color);
+                    }
+                    if let Some(size) = size {
+                        _ = write!(self.body, r#" font-size="{size}""#);
+                    }
+
+                    _ = write!(self.body, ">{}</tspan>", Escaped(source));
+                }
+            },
+        }
+
+        self.body.push_str("</text>\n");
+    }
+
+    fn draw_image(&mut self, image: &Image, bounds: Rect<f32>, quality: ImageQuality) {
+        let Some(href) = data_url(image) else {
+            return;
+        };
+        let Rect { origin, size } = bounds;
+
+        self.indent();
+        _ = write!(
+            self.body,
+            r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none""#,
+            origin.x, origin.y, size.w, size.h,
+        );
+        write_image_rendering(&mut self.body, quality);
+        _ = writeln!(self.body, r#" xlink:href="{href}"/>"#);
+    }
+
+    fn push_layer(&mut self, layer: &Layer) {
+        let Layer { transform, blend_mode, clip, alpha } = layer;
+
+        self.indent();
+        self.body.push_str("<g");
+        write_transform(&mut self.body, "transform", *transform);
+
+        if *alpha != 1.0 {
+            _ = write!(self.body, r#" opacity="{alpha}""#);
+        }
+
+        if let Some(clip) = clip {
+            let id = self.id("clip");
+
+            _ = write!(self.defs, r#"    <clipPath id="{id}"><path"#);
+            write_path_data(&mut self.defs, clip);
+            self.defs.push_str("/></clipPath>\n");
+
+            _ = write!(self.body, r#" clip-path="url(#{id})""#);
+        }
+
+        // SVG can't express composition
+        if let Some(mix) = mix_blend_mode(blend_mode.mix) {
+            _ = write!(self.body, r#" style="mix-blend-mode: {mix}""#);
+        }
+
+        self.body.push_str(">\n");
+        self.depth += 1;
+    }
+
+    fn pop_layer(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+
+        self.indent();
+        self.body.push_str("</g>\n");
+    }
+}
+
+/// Escapes text for use in XML.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for char in self.0.chars() {
+            match char {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                char => f.write_char(char)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn write_path_data(out: &mut String, path: &Path) {
+    _ = write!(out, r#" d="{}""#, path.to_svg_path_data());
+}
+
+/// Writes a color and, if it isn't opaque, its opacity to `opacity_attr`.
+fn write_color(out: &mut String, attr: &str, opacity_attr: &str, color: Color) {
+    let Color { r, g, b, a } = color;
+    let [r, g, b] = [r, g, b].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+
+    _ = write!(out, r##" {attr}="#{r:02x}{g:02x}{b:02x}""##);
+
+    if a < 1.0 {
+        _ = write!(out, r#" {opacity_attr}="{}""#, a.max(0.0));
+    }
+}
+
+fn write_stops(out: &mut String, stops: &[ColorStop]) {
+    for ColorStop { offset, color } in stops {
+        _ = write!(out, r#"      <stop offset="{offset}""#);
+        write_color(out, "stop-color", "stop-opacity", *color);
+        out.push_str("/>\n");
+    }
+}
+
+/// Writes a transform if it isn't the identity.
+fn write_transform(out: &mut String, attr: &str, transform: Affine2<f32>) {
+    if transform == Affine2::IDENTITY {
+        return;
+    }
+
+    let Affine2 { transform: Mat2 { x, y }, translation } = transform;
+
+    _ = write!(
+        out,
+        r#" {attr}="matrix({} {} {} {} {} {})""#,
+        x.x, x.y, y.x, y.y, translation.x, translation.y,
+    );
+}
+
+fn write_font_family(out: &mut String, family: &FontFamily, fallback: &[FontFamily]) {
+    out.push_str(r#" font-family=""#);
+
+    for (i, family) in [family].into_iter().chain(fallback).enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+
+        _ = match family {
+            FontFamily::SansSerif => write!(out, "sans-serif"),
+            FontFamily::Serif => write!(out, "serif"),
+            FontFamily::Monospace => write!(out, "monospace"),
+            FontFamily::Named(name) => write!(out, "'{}'", Escaped(name)),
+        };
+    }
+
+    out.push('"');
+}
+
+fn write_image_rendering(out: &mut String, quality: ImageQuality) {
+    match quality {
+        ImageQuality::Low => out.push_str(r#" image-rendering="optimizeSpeed""#),
+        ImageQuality::Medium => {},
+        ImageQuality::High => out.push_str(r#" image-rendering="optimizeQuality""#),
+    }
+}
+
+/// Returns a `data:` URL of an image encoded as PNG.
+fn data_url(image: &Image) -> Option<String> {
+    let png = image.encode_png(&Default::default()).ok()?;
+
+    Some(format!("data:image/png;base64,{}", base64(&png)))
+}
+
+fn spread_method(extend: Extend) -> &'static str {
+    match extend {
+        Extend::Pad => "pad",
+        Extend::Repeat => "repeat",
+        Extend::Reflect => "reflect",
+    }
+}
+
+/// Returns the value of the CSS `mix-blend-mode` property, or `None` if it is
+/// the default.
+fn mix_blend_mode(mix: Mix) -> Option<&'static str> {
+    Some(match mix {
+        Mix::Normal | Mix::Clip => return None,
+        Mix::Multiply => "multiply",
+        Mix::Screen => "screen",
+        Mix::Overlay => "overlay",
+        Mix::Darken => "darken",
+        Mix::Lighten => "lighten",
+        Mix::ColorDodge => "color-dodge",
+        Mix::ColorBurn => "color-burn",
+        Mix::HardLight => "hard-light",
+        Mix::SoftLight => "soft-light",
+        Mix::Difference => "difference",
+        Mix::Exclusion => "exclusion",
+        Mix::Hue => "hue",
+        Mix::Saturation => "saturation",
+        Mix::Color => "color",
+        Mix::Luminosity => "luminosity",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{Point2, Vec2};
+
+    fn square() -> Rect<f32> {
+        Rect::new(Point2::new(0.0, 0.0), Size2::new(10.0, 10.0))
+    }
+
+    #[test]
+    fn fills_and_strokes_are_written_as_paths() {
+        let mut scene = Scene::new();
+
+        scene.fill(
+            square(),
+            Color::rgba(1.0, 0.0, 0.0, 0.5),
+            FillStyle { rule: FillRule::EvenOdd },
+        );
+        scene.stroke(
+            square(),
+            Color::BLACK,
+            StrokeStyle { width: 2.0, join: Join::Miter, start: Cap::Butt, ..Default::default() },
+        );
+        scene.stroke(
+            square(),
+            Color::BLACK,
+            StrokeStyle::new(1.0).with_dash_array([4.0, 2.0]).with_dash_offset(1.0),
+        );
+
+        let svg = scene.to_svg(Size2::new(20.0, 20.0));
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r##"<path d="M0 0 L10 0 L10 10 L0 10 Z" fill="#ff0000" fill-opacity="0.5" fill-rule="evenodd"/>"##));
+        assert!(svg.contains(r#"stroke-width="2" stroke-linecap="butt" stroke-linejoin="miter" stroke-miterlimit="4""#));
+        assert!(svg.contains(r#"stroke-dasharray="4 2" stroke-dashoffset="1""#));
+    }
+
+    #[test]
+    fn layers_are_written_as_groups() {
+        let mut scene = Scene::new();
+
+        scene.push_layer(Layer {
+            transform: Affine2::from_translation(Vec2::new(5.0, 5.0)),
+            blend_mode: Mix::Multiply.into(),
+            clip: Some(square().into()),
+            alpha: 0.5,
+        });
+        scene.fill(
+            square(),
+            LinearGradient {
+                end: Point2::new(10.0, 0.0),
+                stops: vec![
+                    ColorStop { offset: 0.0, color: Color::WHITE },
+                    ColorStop { offset: 1.0, color: Color::rgba(0.0, 0.0, 0.0, 0.25) },
+                ],
+                ..Default::default()
+            },
+            FillStyle::default(),
+        );
+
+        // the unclosed layer is closed
+        let svg = scene.to_svg(Size2::new(20.0, 20.0));
+
+        assert!(svg.contains(r#"<clipPath id="clip0">"#));
+        assert!(svg.contains(r#"<linearGradient id="paint1" gradientUnits="userSpaceOnUse" x1="0" y1="0" x2="10" y2="0" spreadMethod="pad">"#));
+        assert!(svg.contains(r#"<g transform="matrix(1 0 0 1 5 5)" opacity="0.5" clip-path="url(#clip0)" style="mix-blend-mode: multiply">"#));
+        assert!(svg.contains(r##"<stop offset="0" stop-color="#ffffff"/>"##));
+        assert!(svg.contains(r##"<stop offset="1" stop-color="#000000" stop-opacity="0.25"/>"##));
+        assert!(svg.contains(r#"fill="url(#paint1)""#));
+        assert!(svg.ends_with("  </g>\n</svg>\n"));
+    }
+
+    #[test]
+    fn rich_text_is_written_as_spans() {
+        let mut scene = Scene::new();
+
+        scene.draw_text(
+            [Span::new("a < "), Span::new("b").with_bold()],
+            square(),
+            TextStyle::default(),
+        );
+
+        let svg = scene.to_svg(Size2::new(20.0, 20.0));
+
+        assert!(svg.contains(r#"<tspan>a &lt; </tspan><tspan font-weight="700">b</tspan></text>"#));
+    }
+}
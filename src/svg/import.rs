@@ -0,0 +1,416 @@
+use thiserror::Error;
+use usvg::tiny_skia_path::{self, PathSegment};
+
+use crate::element::{
+    BlendMode,
+    Brush,
+    Cap,
+    Color,
+    ColorStop,
+    Extend,
+    FillRule,
+    FillStyle,
+    Join,
+    Layer,
+    LinearGradient,
+    Mix,
+    Path,
+    PathBuilder,
+    RadialGradient,
+    StrokeStyle,
+};
+use crate::math::{Affine2, Mat2, Point2, Size2, Vec2};
+use crate::Scene;
+
+/// Error when parsing an SVG document.
+#[derive(Debug, Error)]
+pub enum SvgError {
+    #[error(transparent)]
+    Parse(#[from] usvg::Error),
+}
+
+impl Scene {
+    /// Parses an SVG document into a scene.
+    ///
+    /// Returns the scene and the size of the document. Paths, basic shapes,
+    /// groups, solid colors, linear and radial gradients, opacity, blend modes,
+    /// clip paths and stroke properties are supported. Text, images, patterns,
+    /// masks and filters are skipped.
+    pub fn from_svg(svg: &str) -> Result<(Self, Size2<f32>), SvgError> {
+        let tree = usvg::Tree::from_str(svg, &Default::default())?;
+        let size = tree.size();
+
+        let mut scene = Scene::new();
+
+        import_group(&mut scene, tree.root());
+
+        Ok((scene, Size2::new(size.width(), size.height())))
+    }
+}
+
+fn import_group(scene: &mut Scene, group: &usvg::Group) {
+    let transform = to_affine2(group.transform());
+    let alpha = group.opacity().get();
+    let blend_mode = to_blend_mode(group.blend_mode());
+
+    // a clip path can itself be clipped, which is done with nested layers
+    let mut clips = Vec::new();
+    let mut next_clip = group.clip_path();
+
+    while let Some(clip) = next_clip {
+        clips.push(clip_to_path(clip));
+        next_clip = clip.clip_path();
+    }
+
+    let needs_layer = transform != Affine2::IDENTITY
+        || alpha != 1.0
+        || group.blend_mode() != usvg::BlendMode::Normal
+        || !clips.is_empty();
+    let mut clips = clips.into_iter();
+    let mut layers = 0;
+
+    if needs_layer {
+        scene.push_layer(Layer { transform, blend_mode, clip: clips.next(), alpha });
+        layers += 1;
+    }
+
+    for clip in clips {
+        scene.push_layer(Layer {
+            transform: Affine2::IDENTITY,
+            blend_mode: BlendMode::default(),
+            clip: Some(clip),
+            alpha: 1.0,
+        });
+        layers += 1;
+    }
+
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(group) => import_group(scene, group),
+            usvg::Node::Path(path) => import_path(scene, path),
+            usvg::Node::Image(_) | usvg::Node::Text(_) => {},
+        }
+    }
+
+    for _ in 0..layers {
+        scene.pop_layer().expect("layers were pushed above");
+    }
+}
+
+fn import_path(scene: &mut Scene, path: &usvg::Path) {
+    if !path.is_visible() {
+        return;
+    }
+
+    let data = to_path(path.data());
+
+    let fill = |scene: &mut Scene| {
+        let Some(fill) = path.fill() else {
+            return;
+        };
+        let Some(brush) = to_brush(fill.paint(), fill.opacity().get()) else {
+            return;
+        };
+        let rule = match fill.rule() {
+            usvg::FillRule::NonZero => FillRule::NonZero,
+            usvg::FillRule::EvenOdd => FillRule::EvenOdd,
+        };
+
+        scene.fill(data.clone(), brush, FillStyle { rule });
+    };
+    let stroke = |scene: &mut Scene| {
+        let Some(stroke) = path.stroke() else {
+            return;
+        };
+        let Some(brush) = to_brush(stroke.paint(), stroke.opacity().get()) else {
+            return;
+        };
+        let cap = match stroke.linecap() {
+            usvg::LineCap::Butt => Cap::Butt,
+            usvg::LineCap::Round => Cap::Round,
+            usvg::LineCap::Square => Cap::Square,
+        };
+        let join = match stroke.linejoin() {
+            usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => Join::Miter,
+            usvg::LineJoin::Round => Join::Round,
+            usvg::LineJoin::Bevel => Join::Bevel,
+        };
+
+        scene.stroke(
+            data.clone(),
+            brush,
+            StrokeStyle {
+                width: stroke.width().get(),
+                join,
+                start: cap,
+                end: cap,
+                miter_limit: stroke.miterlimit().get(),
+                // usvg repeats odd dash arrays and removes invalid ones
+                dash_array: stroke.dasharray().map(Into::into),
+                dash_offset: stroke.dashoffset(),
+            },
+        );
+    };
+
+    match path.paint_order() {
+        usvg::PaintOrder::FillAndStroke => {
+            fill(scene);
+            stroke(scene);
+        },
+        usvg::PaintOrder::StrokeAndFill => {
+            stroke(scene);
+            fill(scene);
+        },
+    }
+}
+
+/// Returns the brush for a paint, or `None` if it isn't supported.
+fn to_brush(paint: &usvg::Paint, opacity: f32) -> Option<Brush> {
+    let stops = |stops: &[usvg::Stop]| {
+        stops
+            .iter()
+            .map(|stop| ColorStop {
+                offset: stop.offset().get(),
+                color: to_color(stop.color(), stop.opacity().get() * opacity),
+            })
+            .collect()
+    };
+    let extend = |spread_method| match spread_method {
+        usvg::SpreadMethod::Pad => Extend::Pad,
+        usvg::SpreadMethod::Reflect => Extend::Reflect,
+        usvg::SpreadMethod::Repeat => Extend::Repeat,
+    };
+
+    Some(match paint {
+        usvg::Paint::Color(color) => to_color(*color, opacity).into(),
+        usvg::Paint::LinearGradient(gradient) => {
+            let transform = gradient.transform();
+
+            LinearGradient {
+                start: transform_point(transform, gradient.x1(), gradient.y1()),
+                end: transform_point(transform, gradient.x2(), gradient.y2()),
+                extend: extend(gradient.spread_method()),
+                stops: stops(gradient.stops()),
+            }
+            .into()
+        },
+        usvg::Paint::RadialGradient(gradient) => {
+            let transform = gradient.transform();
+            // non-uniform scales and skews can't be represented, so the radius is scaled by
+            // the average scale of the transform
+            let scale = (transform.sx * transform.sy - transform.kx * transform.ky).abs().sqrt();
+
+            RadialGradient {
+                start_center: transform_point(transform, gradient.fx(), gradient.fy()),
+                start_radius: 0.0,
+                end_center: transform_point(transform, gradient.cx(), gradient.cy()),
+                end_radius: gradient.r().get() * scale,
+                extend: extend(gradient.spread_method()),
+                stops: stops(gradient.stops()),
+            }
+            .into()
+        },
+        usvg::Paint::Pattern(_) => return None,
+    })
+}
+
+/// The tolerance of the union of the paths in a clip path, in user units.
+const CLIP_TOLERANCE: f32 = 0.01;
+
+/// Returns the union of the paths in a clip path.
+fn clip_to_path(clip: &usvg::ClipPath) -> Path {
+    fn collect(clip: &mut Path, group: &usvg::Group, transform: tiny_skia_path::Transform) {
+        let transform = transform.pre_concat(group.transform());
+
+        for node in group.children() {
+            match node {
+                usvg::Node::Group(group) => collect(clip, group, transform),
+                usvg::Node::Path(path) => {
+                    let Some(data) = path.data().clone().transform(transform) else {
+                        continue;
+                    };
+                    // the clip rule of a child is its fill rule
+                    let rule = match path.fill().map(usvg::Fill::rule) {
+                        Some(usvg::FillRule::EvenOdd) => FillRule::EvenOdd,
+                        _ => FillRule::NonZero,
+                    };
+
+                    // children can overlap with any winding, so they're merged one at a time
+                    *clip = clip.union(&to_path(&data), rule, CLIP_TOLERANCE);
+                },
+                usvg::Node::Image(_) | usvg::Node::Text(_) => {},
+            }
+        }
+    }
+
+    let mut path = Path::new();
+
+    collect(&mut path, clip.root(), clip.transform());
+
+    path
+}
+
+fn to_path(path: &tiny_skia_path::Path) -> Path {
+    let mut builder = PathBuilder::new();
+
+    append(&mut builder, path);
+
+    // subpaths without a close are open
+    builder.build_open()
+}
+
+fn append(builder: &mut PathBuilder, path: &tiny_skia_path::Path) {
+    let point = |p: tiny_skia_path::Point| Point2::new(p.x, p.y);
+
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => builder.open(point(p)),
+            PathSegment::LineTo(p) => builder.line_to(point(p)),
+            PathSegment::QuadTo(c, p) => builder.quad_to(point(c), point(p)),
+            PathSegment::CubicTo(c1, c2, p) => builder.cubic_to(point(c1), point(c2), point(p)),
+            PathSegment::Close => builder.close(),
+        }
+    }
+}
+
+fn to_color(color: usvg::Color, opacity: f32) -> Color {
+    let usvg::Color { red, green, blue } = color;
+
+    Color::rgba(red as f32 / 255.0, green as f32 / 255.0, blue as f32 / 255.0, opacity)
+}
+
+fn to_affine2(transform: tiny_skia_path::Transform) -> Affine2<f32> {
+    let tiny_skia_path::Transform { sx, ky, kx, sy, tx, ty } = transform;
+
+    Affine2::new(Mat2::from_columns(Vec2::new(sx, ky), Vec2::new(kx, sy)), Vec2::new(tx, ty))
+}
+
+fn transform_point(transform: tiny_skia_path::Transform, x: f32, y: f32) -> Point2<f32> {
+    let mut point = tiny_skia_path::Point::from_xy(x, y);
+
+    transform.map_point(&mut point);
+
+    Point2::new(point.x, point.y)
+}
+
+fn to_blend_mode(blend_mode: usvg::BlendMode) -> BlendMode {
+    let mix = match blend_mode {
+        // isolated groups in SVG use normal blending
+        usvg::BlendMode::Normal => return BlendMode::new(Mix::Normal, Default::default()),
+        usvg::BlendMode::Multiply => Mix::Multiply,
+        usvg::BlendMode::Screen => Mix::Screen,
+        usvg::BlendMode::Overlay => Mix::Overlay,
+        usvg::BlendMode::Darken => Mix::Darken,
+        usvg::BlendMode::Lighten => Mix::Lighten,
+        usvg::BlendMode::ColorDodge => Mix::ColorDodge,
+        usvg::BlendMode::ColorBurn => Mix::ColorBurn,
+        usvg::BlendMode::HardLight => Mix::HardLight,
+        usvg::BlendMode::SoftLight => Mix::SoftLight,
+        usvg::BlendMode::Difference => Mix::Difference,
+        usvg::BlendMode::Exclusion => Mix::Exclusion,
+        usvg::BlendMode::Hue => Mix::Hue,
+        usvg::BlendMode::Saturation => Mix::Saturation,
+        usvg::BlendMode::Color => Mix::Color,
+        usvg::BlendMode::Luminosity => Mix::Luminosity,
+    };
+
+    mix.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::PathElement;
+    use crate::Command;
+
+    #[test]
+    fn shapes_are_imported_as_fills_and_strokes() {
+        let (scene, size) = Scene::from_svg(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+                <rect width="10" height="10" fill="#ff0000" fill-opacity="0.5"/>
+                <line x1="0" y1="0" x2="10" y2="10" fill="none" stroke="blue" stroke-width="2" stroke-linecap="square" stroke-dasharray="3 1 2" stroke-dashoffset="1"/>
+            </svg>"##,
+        )
+        .unwrap();
+
+        assert_eq!(size, Size2::new(20.0, 10.0));
+
+        let [Command::Fill { path, brush, .. }, Command::Stroke { brush: stroke, style, .. }] =
+            scene.commands()
+        else {
+            panic!("unexpected commands: {:?}", scene.commands());
+        };
+
+        assert_eq!(path.iter().next(), Some(PathElement::Open(Point2::new(0.0, 0.0))));
+        assert_eq!(brush, &Brush::Solid(Color::rgba(1.0, 0.0, 0.0, 0.5)));
+        assert_eq!(stroke, &Brush::Solid(Color::BLUE));
+        assert_eq!((style.width, style.start, style.end), (2.0, Cap::Square, Cap::Square));
+        assert_eq!(style.dash_array.as_deref(), Some(&[3.0, 1.0, 2.0, 3.0, 1.0, 2.0][..]));
+        assert_eq!(style.dash_offset, 1.0);
+    }
+
+    #[test]
+    fn groups_are_imported_as_layers() {
+        let (scene, _) = Scene::from_svg(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                <defs>
+                    <clipPath id="clip"><rect width="5" height="5"/></clipPath>
+                    <linearGradient id="gradient" x2="10" gradientUnits="userSpaceOnUse">
+                        <stop offset="0" stop-color="white"/>
+                        <stop offset="1" stop-color="black"/>
+                    </linearGradient>
+                </defs>
+                <g transform="translate(1 2)" opacity="0.5" clip-path="url(#clip)">
+                    <rect width="10" height="10" fill="url(#gradient)"/>
+                </g>
+            </svg>"##,
+        )
+        .unwrap();
+
+        let [Command::PushLayer(layer), Command::Fill { brush, .. }, Command::PopLayer] =
+            scene.commands()
+        else {
+            panic!("unexpected commands: {:?}", scene.commands());
+        };
+
+        assert_eq!(layer.transform, Affine2::from_translation(Vec2::new(1.0, 2.0)));
+        assert_eq!(layer.alpha, 0.5);
+        assert!(layer.clip.is_some());
+
+        let Brush::LinearGradient(gradient) = brush else {
+            panic!("expected a linear gradient, found {brush:?}");
+        };
+
+        assert_eq!((gradient.start, gradient.end), (Point2::new(0.0, 0.0), Point2::new(10.0, 0.0)));
+        assert_eq!(gradient.stops.len(), 2);
+    }
+
+    #[test]
+    fn clip_paths_are_the_union_of_their_children() {
+        // the rects wind in opposite directions
+        let (scene, _) = Scene::from_svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+                <clipPath id="clip">
+                    <path d="M0 0 H10 V10 H0 Z"/>
+                    <path d="M5 0 V10 H15 V0 Z"/>
+                </clipPath>
+                <rect width="20" height="10" clip-path="url(#clip)"/>
+            </svg>"#,
+        )
+        .unwrap();
+
+        let Command::PushLayer(Layer { clip: Some(clip), .. }) = &scene.commands()[0] else {
+            panic!("unexpected commands: {:?}", scene.commands());
+        };
+
+        for x in [2.0, 7.0, 12.0] {
+            assert!(clip.contains(Point2::new(x, 5.0), FillRule::NonZero));
+        }
+        assert!(!clip.contains(Point2::new(17.0, 5.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn invalid_documents_are_an_error() {
+        assert!(Scene::from_svg("<svg").is_err());
+    }
+}
@@ -0,0 +1,6 @@
+//! Conversion between [`Scene`](crate::Scene)s and SVG documents.
+
+pub use self::import::*;
+
+mod export;
+mod import;
@@ -0,0 +1,428 @@
+//! Conversion of [`Path`]s and [`Scene`]s into triangle meshes.
+//!
+//! Meshes can be drawn by pipelines that only understand triangles, where a
+//! [`Renderer`](crate::Renderer) can't be used.
+
+use std::collections::HashMap;
+
+use crate::element::{FillRule, Layer, Path, StrokeStyle};
+use crate::math::{Affine2, Point2, Polyline};
+use crate::util::max_scale;
+use crate::{Command, Scene};
+
+/// An indexed triangle mesh.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Mesh {
+    /// The positions of the vertices.
+    pub vertices: Vec<Point2<f32>>,
+    /// Indices into [`Mesh::vertices`], 3 per triangle.
+    ///
+    /// Triangles are clockwise (`y`-down).
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+    /// Returns an iterator of the triangles of this mesh.
+    pub fn triangles(&self) -> impl Iterator<Item = [Point2<f32>; 3]> + '_ {
+        self.indices
+            .chunks_exact(3)
+            .map(|indices| [0, 1, 2].map(|i| self.vertices[indices[i] as usize]))
+    }
+
+    fn vertex(&mut self, cache: &mut HashMap<(u32, u32), u32>, point: Point2<f32>) -> u32 {
+        *cache.entry((point.x.to_bits(), point.y.to_bits())).or_insert_with(|| {
+            self.vertices.push(point);
+
+            (self.vertices.len() - 1) as u32
+        })
+    }
+}
+
+/// Returns a mesh covering the area of a path filled with a rule.
+///
+/// Curves are flattened into lines that deviate from them by at most
+/// `tolerance`.
+pub fn fill(path: &Path, rule: FillRule, tolerance: f32) -> Mesh {
+    // resolves overlaps and intersections, leaving edges that only meet at
+    // their ends
+    triangulate(&path.union(&Path::new(), rule, tolerance), tolerance)
+}
+
+/// Returns a mesh covering the area of a stroke of a path.
+///
+/// Curves, round joins and round caps are flattened into lines that deviate
+/// from them by at most `tolerance`.
+pub fn stroke(path: &Path, style: &StrokeStyle, tolerance: f32) -> Mesh {
+    // outlines have no overlaps
+    triangulate(&path.stroke_outline(style, tolerance), tolerance)
+}
+
+/// An open layer while tessellating a scene.
+struct SceneLayer {
+    /// The transform from the layer to the scene.
+    transform: Affine2<f32>,
+    /// The area of the scene the layer is clipped to, or `None` if it isn't
+    /// clipped.
+    clip: Option<Path>,
+}
+
+/// Returns an iterator of the meshes of the fills and strokes of a scene,
+/// with the indices of their commands.
+///
+/// Paths are transformed and clipped by their layers, so meshes are in the
+/// space of the scene, and curves are flattened into lines that deviate from
+/// them by at most `tolerance` in it. Text, images, alpha and blend modes are
+/// ignored, and commands that are clipped out entirely are skipped.
+pub fn scene(scene: &Scene, tolerance: f32) -> impl Iterator<Item = (usize, Mesh)> + '_ {
+    let mut layers = vec![SceneLayer { transform: Affine2::IDENTITY, clip: None }];
+
+    scene.commands.iter().enumerate().filter_map(move |(i, command)| {
+        let SceneLayer { transform, clip } = layers.last()?;
+        let transform = *transform;
+        let visible = !clip.as_ref().is_some_and(Path::is_empty);
+
+        let area = match command {
+            Command::Fill { path, style, .. } if visible => {
+                let path = path.clone().transformed(transform);
+
+                match clip {
+                    Some(clip) => path.intersection(clip, style.rule, tolerance),
+                    None => path.union(&Path::new(), style.rule, tolerance),
+                }
+            },
+            Command::Stroke { path, style, .. } if visible => {
+                // outlines are flattened in the space of the layer, finely enough for
+                // the direction it's stretched the most in
+                let outline = path
+                    .stroke_outline(style, tolerance / max_scale(transform.transform))
+                    .transformed(transform);
+
+                match clip {
+                    Some(clip) => outline.intersection(clip, FillRule::NonZero, tolerance),
+                    None => outline,
+                }
+            },
+            Command::PushLayer(Layer { transform: layer_transform, clip: layer_clip, .. }) => {
+                let transform = transform * *layer_transform;
+                // layers that can't be inverted have no area
+                let clip = if transform.inverse().is_none() {
+                    Some(Path::new())
+                } else {
+                    match (clip, layer_clip) {
+                        (Some(clip), Some(layer_clip)) => Some(clip.intersection(
+                            &layer_clip.clone().transformed(transform),
+                            FillRule::NonZero,
+                            tolerance,
+                        )),
+                        (None, Some(layer_clip)) => {
+                            Some(layer_clip.clone().transformed(transform).union(
+                                &Path::new(),
+                                FillRule::NonZero,
+                                tolerance,
+                            ))
+                        },
+                        (clip, None) => clip.clone(),
+                    }
+                };
+
+                layers.push(SceneLayer { transform, clip });
+
+                return None;
+            },
+            Command::PopLayer => {
+                // the root layer is never popped
+                if layers.len() > 1 {
+                    layers.pop();
+                }
+
+                return None;
+            },
+            _ => return None,
+        };
+
+        let mesh = triangulate(&area, tolerance);
+
+        (!mesh.indices.is_empty()).then_some((i, mesh))
+    })
+}
+
+/// An edge with `from` above `to`.
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    from: Point2<f32>,
+    to: Point2<f32>,
+    /// `1` if the edge goes down, `-1` otherwise.
+    winding: i32,
+}
+
+impl Edge {
+    /// Returns the `x` coordinate of this edge at `y`.
+    ///
+    /// Exact at the ends, so that edges meeting at a point agree on it.
+    fn x_at(&self, y: f32) -> f32 {
+        if y == self.from.y {
+            self.from.x
+        } else if y == self.to.y {
+            self.to.x
+        } else {
+            let t = (y - self.from.y) / (self.to.y - self.from.y);
+
+            self.from.x + (self.to.x - self.from.x) * t
+        }
+    }
+}
+
+/// Triangulates a path whose subpaths don't cross each other, filling where
+/// the winding number isn't `0`.
+///
+/// The path is split into horizontal bands at the `y` of every vertex, and
+/// the filled parts of each band are trapezoids.
+fn triangulate(path: &Path, tolerance: f32) -> Mesh {
+    let mut edges = Vec::new();
+
+    for Polyline { points, .. } in path.flatten(tolerance) {
+        for (i, &from) in points.iter().enumerate() {
+            let to = points[(i + 1) % points.len()];
+
+            if from.y < to.y {
+                edges.push(Edge { from, to, winding: 1 });
+            } else if to.y < from.y {
+                edges.push(Edge { from: to, to: from, winding: -1 });
+            }
+        }
+    }
+
+    edges.sort_by(|a, b| a.from.y.total_cmp(&b.from.y));
+
+    let mut levels: Vec<_> = edges.iter().flat_map(|edge| [edge.from.y, edge.to.y]).collect();
+
+    levels.sort_by(f32::total_cmp);
+    levels.dedup();
+
+    // the edges crossing each band, with their `x` at its top and bottom
+    let mut bands = Vec::with_capacity(levels.len().saturating_sub(1));
+    let mut active: Vec<Edge> = Vec::new();
+    let mut next = 0;
+
+    for pair in levels.windows(2) {
+        let (top, bottom) = (pair[0], pair[1]);
+
+        active.retain(|edge| edge.to.y > top);
+
+        while next < edges.len() && edges[next].from.y <= top {
+            active.push(edges[next]);
+            next += 1;
+        }
+
+        let mut band: Vec<_> =
+            active.iter().map(|edge| (edge.x_at(top), edge.x_at(bottom), edge.winding)).collect();
+
+        band.sort_by(|a, b| (a.0 + a.1).total_cmp(&(b.0 + b.1)));
+        bands.push(band);
+    }
+
+    // every point on each level, so that trapezoids include the vertices of
+    // their neighbors
+    let mut points = vec![Vec::new(); levels.len()];
+
+    for (i, band) in bands.iter().enumerate() {
+        for &(x0, x1, _) in band {
+            points[i].push(x0);
+            points[i + 1].push(x1);
+        }
+    }
+
+    for points in &mut points {
+        points.sort_by(f32::total_cmp);
+        points.dedup();
+    }
+
+    let mut mesh = Mesh::default();
+    let mut cache = HashMap::new();
+
+    for (i, band) in bands.iter().enumerate() {
+        let mut winding = 0;
+
+        for pair in band.windows(2) {
+            let ((left_top, left_bottom, edge_winding), (right_top, right_bottom, _)) =
+                (pair[0], pair[1]);
+
+            winding += edge_winding;
+
+            if winding == 0 {
+                continue;
+            }
+
+            let range = |points: &[f32], left: f32, right: f32| {
+                let start = points.partition_point(|&x| x < left);
+                let end = points.partition_point(|&x| x <= right);
+
+                points[start..end.max(start)].to_vec()
+            };
+            let top: Vec<_> = range(&points[i], left_top, right_top)
+                .into_iter()
+                .map(|x| Point2::new(x, levels[i]))
+                .collect();
+            let bottom: Vec<_> = range(&points[i + 1], left_bottom, right_bottom)
+                .into_iter()
+                .map(|x| Point2::new(x, levels[i + 1]))
+                .collect();
+
+            trapezoid(&mut mesh, &mut cache, &top, &bottom);
+        }
+    }
+
+    mesh
+}
+
+/// Adds triangles between points along the top and bottom of a trapezoid,
+/// sorted by `x`.
+fn trapezoid(
+    mesh: &mut Mesh,
+    cache: &mut HashMap<(u32, u32), u32>,
+    top: &[Point2<f32>],
+    bottom: &[Point2<f32>],
+) {
+    if top.is_empty() || bottom.is_empty() {
+        return;
+    }
+
+    let (mut i, mut j) = (0, 0);
+
+    // advance along whichever side is further behind
+    while i + 1 < top.len() || j + 1 < bottom.len() {
+        let triangle =
+            if j + 1 == bottom.len() || (i + 1 < top.len() && top[i + 1].x <= bottom[j + 1].x) {
+                i += 1;
+
+                [top[i - 1], top[i], bottom[j]]
+            } else {
+                j += 1;
+
+                [top[i], bottom[j], bottom[j - 1]]
+            };
+
+        for point in triangle {
+            let index = mesh.vertex(cache, point);
+
+            mesh.indices.push(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+    use crate::element::{Cap, Color, FillStyle, TextStyle};
+    use crate::math::{Circle, Line, Rect, Size2, Vec2};
+
+    fn area(mesh: &Mesh) -> f32 {
+        mesh.triangles()
+            .map(|[a, b, c]| ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)) / 2.0)
+            .inspect(|&area| assert!(area >= 0.0, "counter-clockwise triangle"))
+            .sum()
+    }
+
+    #[test]
+    fn fill_rect() {
+        let rect = Path::from(Rect::new(Point2::new(0.0, 0.0), Size2::new(4.0, 2.0)));
+        let mesh = fill(&rect, FillRule::NonZero, 0.01);
+
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.indices.len(), 6);
+        assert_eq!(area(&mesh), 8.0);
+        assert_eq!(fill(&Path::new(), FillRule::NonZero, 0.01), Mesh::default());
+    }
+
+    #[test]
+    fn fill_rules() {
+        let squares = Path::from_fn(|builder| {
+            for (origin, size) in [(0.0, 10.0), (2.0, 6.0)] {
+                builder.open(Point2::new(origin, origin));
+                builder.line_to(Point2::new(origin + size, origin));
+                builder.line_to(Point2::new(origin + size, origin + size));
+                builder.line_to(Point2::new(origin, origin + size));
+                builder.close();
+            }
+        });
+
+        assert_eq!(area(&fill(&squares, FillRule::NonZero, 0.01)), 100.0);
+        assert_eq!(area(&fill(&squares, FillRule::EvenOdd, 0.01)), 64.0);
+
+        let circle =
+            fill(&Path::from(Circle::new(Point2::new(0.0, 0.0), 10.0)), FillRule::NonZero, 1e-3);
+
+        assert!((area(&circle) - PI * 100.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn vertices_are_shared() {
+        // an L, where the vertex of the inner corner is on the boundary between bands
+        let path = Path::from_fn(|builder| {
+            builder.open(Point2::new(0.0, 0.0));
+            builder.line_to(Point2::new(2.0, 0.0));
+            builder.line_to(Point2::new(2.0, 1.0));
+            builder.line_to(Point2::new(4.0, 1.0));
+            builder.line_to(Point2::new(4.0, 2.0));
+            builder.line_to(Point2::new(0.0, 2.0));
+        });
+        let mesh = fill(&path, FillRule::NonZero, 0.01);
+
+        assert_eq!(area(&mesh), 6.0);
+        // the points at `y = 1` are shared by both bands
+        assert_eq!(mesh.vertices.len(), 7);
+        assert_eq!(mesh.indices.len(), 3 * 5);
+    }
+
+    #[test]
+    fn stroke_line() {
+        let line = Path::from(Line::new(Point2::new(0.0, 0.0), Point2::new(10.0, 10.0)));
+        let style = StrokeStyle::new(2.0).with_cap(Cap::Butt);
+
+        assert!((area(&stroke(&line, &style, 0.01)) - 2.0 * 200f32.sqrt()).abs() < 1e-3);
+        assert!(stroke(&line, &style.with_width(0.0), 0.01).indices.is_empty());
+    }
+
+    #[test]
+    fn scenes_are_transformed_and_clipped() {
+        let mut scene = Scene::new();
+        let square = Rect::new(Point2::new(0.0, 0.0), Size2::splat(10.0));
+        let clip = Rect::new(Point2::new(0.0, 0.0), Size2::splat(5.0));
+        let layer = |transform, clip: Option<Path>| Layer {
+            transform,
+            blend_mode: Default::default(),
+            clip,
+            alpha: 1.0,
+        };
+        let transform =
+            Affine2::from_scale(Vec2::new(2.0, 1.0)).then_translate(Vec2::new(100.0, 0.0));
+
+        scene.with_layer(layer(transform, Some(clip.into())), |scene| {
+            scene.fill(square, Color::BLACK, FillStyle::default());
+            scene.stroke(
+                Line::new(Point2::new(0.0, 2.0), Point2::new(10.0, 2.0)),
+                Color::BLACK,
+                StrokeStyle::new(2.0).with_cap(Cap::Butt),
+            );
+            scene.with_layer(layer(Affine2::from_scale(Vec2::splat(0.0)), None), |scene| {
+                scene.fill(square, Color::BLACK, FillStyle::default());
+            });
+        });
+        scene.draw_text("text", square, TextStyle::default());
+        scene.fill(square, Color::BLACK, FillStyle::default());
+
+        let meshes: Vec<_> = super::scene(&scene, 0.01).collect();
+        let indices: Vec<_> = meshes.iter().map(|(i, _)| *i).collect();
+
+        assert_eq!(indices, [1, 2, 8]);
+        // the layer covers `100..120` by `0..10`, but is clipped to `100..110` by
+        // `0..5`
+        assert_eq!(area(&meshes[0].1), 50.0);
+        assert!(meshes[0].1.vertices.iter().all(|point| point.x >= 100.0 && point.y <= 5.0));
+        // the stroke is stretched to `20` long, then clipped to `10`
+        assert_eq!(area(&meshes[1].1), 20.0);
+        assert_eq!(area(&meshes[2].1), 100.0);
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::math::Mat2;
+
 /// Implements [`std::fmt::Debug`] using an implementation of
 /// [`std::fmt::Display`].
 pub(crate) struct DisplayDebug<'a, T: ?Sized>(pub &'a T);
@@ -9,3 +11,64 @@ impl<T: fmt::Display + ?Sized> fmt::Debug for DisplayDebug<'_, T> {
         self.0.fmt(f)
     }
 }
+
+/// Returns the most a matrix stretches any vector, its largest singular value.
+///
+/// Tolerances in device space are divided by this to get tolerances in user
+/// space that are small enough in every direction, even under skews and
+/// non-uniform scales.
+pub(crate) fn max_scale(matrix: Mat2<f32>) -> f32 {
+    let Mat2 { x, y } = matrix;
+    let half_norm = (x.x * x.x + x.y * x.y + y.x * y.x + y.y * y.y) / 2.0;
+    let determinant = matrix.determinant();
+
+    (half_norm + (half_norm * half_norm - determinant * determinant).max(0.0).sqrt()).sqrt()
+}
+
+/// Encodes bytes as standard base64 with padding.
+#[cfg(feature = "svg")]
+pub(crate) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let n =
+            chunk.iter().enumerate().fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - i * 8));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Vec2;
+
+    #[test]
+    fn max_scale_is_the_largest_stretch() {
+        let scale = Mat2::from_columns(Vec2::new(2.0, 0.0), Vec2::new(0.0, -3.0));
+        let skew = Mat2::from_columns(Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0));
+
+        assert_eq!(max_scale(scale), 3.0);
+        assert!((max_scale(skew) - (1.5 + 1.25f32.sqrt()).sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    #[cfg(feature = "svg")]
+    fn base64_is_padded() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+    }
+}